@@ -595,6 +595,20 @@ impl LineIndex {
         let index = self.0;
         index.checked_sub(rhs).map(LineIndex)
     }
+
+    /// Convert a (half-open) range of line numbers into the corresponding range of line indices.
+    ///
+    /// # Examples:
+    /// ```
+    /// use unsegen::base::{LineIndex, LineNumber};
+    /// assert_eq!(
+    ///     LineIndex::range_from_numbers(LineNumber::new(1)..LineNumber::new(3)),
+    ///     LineIndex::new(0)..LineIndex::new(2)
+    /// );
+    /// ```
+    pub fn range_from_numbers(range: Range<LineNumber>) -> Range<LineIndex> {
+        range.start.into()..range.end.into()
+    }
 }
 
 impl Into<usize> for LineIndex {
@@ -651,8 +665,24 @@ impl LineNumber {
     ///
     /// Panics if val is 0 (as line numbers start from 1).
     pub fn new(val: usize) -> Self {
-        assert!(val > 0, "Invalid LineNumber: Number == 0");
-        LineNumber(val)
+        Self::try_new(val).expect("Invalid LineNumber: Number == 0")
+    }
+
+    /// Create a new LineNumber from a raw value, returning None if val is 0 (as line numbers start
+    /// from 1).
+    ///
+    /// # Examples:
+    /// ```
+    /// use unsegen::base::LineNumber;
+    /// assert_eq!(LineNumber::try_new(1), Some(LineNumber::new(1)));
+    /// assert_eq!(LineNumber::try_new(0), None);
+    /// ```
+    pub fn try_new(val: usize) -> Option<Self> {
+        if val > 0 {
+            Some(LineNumber(val))
+        } else {
+            None
+        }
     }
 
     /// Unpack the LineNumber and yield the underlying value.
@@ -675,6 +705,20 @@ impl LineNumber {
         let index = self.0 - 1; // Safe according to invariant: self.0 >= 1
         index.checked_sub(rhs).map(|i| LineNumber(i + 1))
     }
+
+    /// Convert a (half-open) range of line indices into the corresponding range of line numbers.
+    ///
+    /// # Examples:
+    /// ```
+    /// use unsegen::base::{LineIndex, LineNumber};
+    /// assert_eq!(
+    ///     LineNumber::range_from_indices(LineIndex::new(0)..LineIndex::new(2)),
+    ///     LineNumber::new(1)..LineNumber::new(3)
+    /// );
+    /// ```
+    pub fn range_from_indices(range: Range<LineIndex>) -> Range<LineNumber> {
+        range.start.into()..range.end.into()
+    }
 }
 
 impl Into<usize> for LineNumber {
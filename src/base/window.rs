@@ -78,6 +78,7 @@ type CharMatrixView<'w> = ArrayViewMut<'w, StyledGraphemeCluster, Ix2>;
 pub struct Window<'w> {
     values: CharMatrixView<'w>,
     default_style: Style,
+    background: (GraphemeCluster, StyleModifier),
 }
 
 impl<'w> ::std::fmt::Debug for Window<'w> {
@@ -94,6 +95,7 @@ impl<'w> Window<'w> {
         Window {
             values: values,
             default_style: Style::default(),
+            background: (GraphemeCluster::space(), StyleModifier::new()),
         }
     }
 
@@ -186,6 +188,7 @@ impl<'w> Window<'w> {
         Window {
             values: sub_mat,
             default_style: self.default_style,
+            background: self.background.clone(),
         }
     }
 
@@ -227,10 +230,12 @@ impl<'w> Window<'w> {
             let w_u = Window {
                 values: first_mat,
                 default_style: self.default_style,
+                background: self.background.clone(),
             };
             let w_d = Window {
                 values: second_mat,
                 default_style: self.default_style,
+                background: self.background,
             };
             Ok((w_u, w_d))
         } else {
@@ -255,10 +260,16 @@ impl<'w> Window<'w> {
     /// // Every row of wb now contains two '山', while the last column cotains spaces.
     /// ```
     pub fn fill(&mut self, c: GraphemeCluster) {
+        let style = self.default_style;
+        self.fill_with_style(c, style);
+    }
+
+    /// Fill the window with the specified GraphemeCluster and style.
+    fn fill_with_style(&mut self, c: GraphemeCluster, style: Style) {
         let cluster_width = c.width();
-        let template = StyledGraphemeCluster::new(c, self.default_style);
-        let empty = StyledGraphemeCluster::new(GraphemeCluster::empty(), self.default_style);
-        let space = StyledGraphemeCluster::new(GraphemeCluster::space(), self.default_style);
+        let template = StyledGraphemeCluster::new(c, style);
+        let empty = StyledGraphemeCluster::new(GraphemeCluster::empty(), style);
+        let space = StyledGraphemeCluster::new(GraphemeCluster::space(), style);
         let w: i32 = self.get_width().into();
         let right_border = (w - (w % cluster_width as i32)) as usize;
         for ((_, x), cell) in self.values.indexed_iter_mut() {
@@ -272,9 +283,10 @@ impl<'w> Window<'w> {
         }
     }
 
-    /// Fill the window with space characters.
+    /// Fill the window with its background, i.e., space characters by default.
     ///
     /// The style (i.e., the background color) is defined by the default style of the window.
+    /// Use `set_background` to change the grapheme cluster or style that is used.
     ///
     /// # Examples:
     /// ```
@@ -284,7 +296,67 @@ impl<'w> Window<'w> {
     /// // Every cell of wb now contains a ' '.
     /// ```
     pub fn clear(&mut self) {
-        self.fill(GraphemeCluster::space());
+        let (cluster, modifier) = self.background.clone();
+        let style = modifier.apply(self.default_style);
+        self.fill_with_style(cluster, style);
+    }
+
+    /// Specify the grapheme cluster and style (applied on top of the default style) that will be
+    /// used to fill the window in subsequent calls to `clear`.
+    ///
+    /// The background is inherited by windows created via `create_subwindow` or `split`. This is
+    /// useful for textured backgrounds or to visualize the space allocated to widgets (e.g., in
+    /// layouts) during development.
+    ///
+    /// # Examples:
+    /// ```
+    /// # use unsegen::base::terminal::test::FakeTerminal;
+    /// use unsegen::base::*;
+    ///
+    /// let mut term = FakeTerminal::with_size((3, 2));
+    /// {
+    ///     let mut win = term.create_root_window();
+    ///     win.set_background(GraphemeCluster::try_from('.').unwrap(), StyleModifier::new());
+    ///     let (mut top, _) = win.split(RowIndex::new(1)).unwrap();
+    ///     top.clear();
+    /// }
+    /// term.assert_looks_like("...|   ");
+    /// ```
+    pub fn set_background(&mut self, cluster: GraphemeCluster, style: StyleModifier) {
+        self.background = (cluster, style);
+    }
+
+    /// Call `f` for every cell of the window with its column and row (relative to the window).
+    ///
+    /// Only the region of the underlying buffer that is covered by this window is visited. This is
+    /// useful to post-process content that has already been drawn, e.g., to dim the background
+    /// behind a popup.
+    ///
+    /// # Examples:
+    /// ```
+    /// # use unsegen::base::terminal::test::FakeTerminal;
+    /// use unsegen::base::*;
+    ///
+    /// let mut term = FakeTerminal::with_size((3, 2));
+    /// {
+    ///     let mut win = term.create_root_window();
+    ///     win.fill(GraphemeCluster::try_from('x').unwrap());
+    ///     let (_, mut bottom) = win.split(RowIndex::new(1)).unwrap();
+    ///     bottom.for_each_cell(|col, _row, cell| {
+    ///         if col > 0 {
+    ///             StyleModifier::new().bold(true).modify(&mut cell.style);
+    ///         }
+    ///     });
+    /// }
+    /// term.assert_looks_like("xxx|x*x**x*");
+    /// ```
+    pub fn for_each_cell<F: FnMut(ColIndex, RowIndex, &mut StyledGraphemeCluster)>(
+        &mut self,
+        mut f: F,
+    ) {
+        for ((y, x), cell) in self.values.indexed_iter_mut() {
+            f(ColIndex::new(x as i32), RowIndex::new(y as i32), cell);
+        }
     }
 
     /// Specify the new default style of the window. This style will be applied to all grapheme
@@ -674,3 +674,173 @@ pub trait Editable: Navigatable + Writable {
     /// Remove all content.
     fn clear(&mut self) -> OperationResult;
 }
+
+// KeySequence ------------------------------------------------------
+
+/// The outcome of feeding a single event to a `KeySequence`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SequenceResult<O> {
+    /// The events received so far are a proper prefix of at least one bound sequence. More input
+    /// is required.
+    Pending,
+    /// The events received so far form a complete bound sequence which resolved to the operation.
+    Resolved(O),
+    /// The event neither continues the pending prefix nor starts a bound sequence. The pending
+    /// prefix (if any) has been discarded.
+    Invalid,
+}
+
+/// A small state machine that resolves (multi-key) sequences of events (e.g., `dd` or `gg` in vim)
+/// to high level operations.
+///
+/// Bind sequences using `bind` and either `feed` events manually or use the `behavior` in an
+/// `InputChain`. Note that no sequence should be a prefix of another one, since the shorter one
+/// will always be resolved first.
+///
+/// # Examples:
+/// ```
+/// use unsegen::input::*;
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// enum Op {
+///     DeleteLine,
+///     GoToTop,
+/// }
+///
+/// let mut seq = KeySequence::new()
+///     .bind(&[Key::Char('d'), Key::Char('d')], Op::DeleteLine)
+///     .bind(&[Key::Char('g'), Key::Char('g')], Op::GoToTop);
+///
+/// assert_eq!(seq.feed(Key::Char('g')), SequenceResult::Pending);
+/// assert_eq!(seq.feed(Key::Char('g')), SequenceResult::Resolved(Op::GoToTop));
+/// assert_eq!(seq.feed(Key::Char('d')), SequenceResult::Pending);
+/// assert_eq!(seq.feed(Key::Char('x')), SequenceResult::Invalid);
+/// assert!(seq.pending().is_empty());
+/// ```
+pub struct KeySequence<O> {
+    bindings: Vec<(Vec<Event>, O)>,
+    pending: Vec<Event>,
+    discarded: Vec<Event>,
+}
+
+impl<O: Clone> KeySequence<O> {
+    /// Create a `KeySequence` without any bound sequences.
+    pub fn new() -> Self {
+        KeySequence {
+            bindings: Vec::new(),
+            pending: Vec::new(),
+            discarded: Vec::new(),
+        }
+    }
+
+    /// Resolve the sequence of events to the specified operation.
+    pub fn bind<E: ToEvent + Clone>(mut self, sequence: &[E], operation: O) -> Self {
+        let sequence = sequence.iter().map(|e| e.clone().to_event()).collect();
+        self.bindings.push((sequence, operation));
+        self
+    }
+
+    /// Get the events that have been received, but did not resolve to an operation yet.
+    pub fn pending(&self) -> &[Event] {
+        &self.pending
+    }
+
+    /// Get the pending events that were discarded during the most recent call to `feed` because
+    /// the fed event did not continue them.
+    pub fn discarded(&self) -> &[Event] {
+        &self.discarded
+    }
+
+    /// Discard all pending events.
+    pub fn reset(&mut self) {
+        self.pending.clear();
+    }
+
+    /// Append an event to the pending prefix and check whether it resolves to an operation.
+    ///
+    /// If the event does not continue the pending prefix, the prefix is discarded (see
+    /// `discarded`) and the event is tried again as the start of a new sequence.
+    ///
+    /// # Examples:
+    /// ```
+    /// use unsegen::input::*;
+    ///
+    /// let mut seq = KeySequence::new()
+    ///     .bind(&[Key::Char('d'), Key::Char('d')], "delete line")
+    ///     .bind(&[Key::Char('g'), Key::Char('g')], "go to top");
+    ///
+    /// assert_eq!(seq.feed(Key::Char('d')), SequenceResult::Pending);
+    /// assert_eq!(seq.feed(Key::Char('g')), SequenceResult::Pending);
+    /// assert_eq!(seq.discarded(), &[Key::Char('d').to_event()]);
+    /// assert_eq!(seq.feed(Key::Char('g')), SequenceResult::Resolved("go to top"));
+    /// assert!(seq.discarded().is_empty());
+    /// ```
+    pub fn feed<E: ToEvent>(&mut self, event: E) -> SequenceResult<O> {
+        let event = event.to_event();
+        self.discarded.clear();
+        match self.advance(event.clone()) {
+            SequenceResult::Invalid if !self.pending.is_empty() => {
+                self.discarded = ::std::mem::take(&mut self.pending);
+                self.advance(event)
+            }
+            result => result,
+        }
+    }
+
+    /// Append an event to the pending prefix. If the result is not a prefix of any bound sequence,
+    /// the event is removed again and the prefix is left unchanged.
+    fn advance(&mut self, event: Event) -> SequenceResult<O> {
+        self.pending.push(event);
+        let mut is_prefix = false;
+        for (sequence, operation) in self.bindings.iter() {
+            if sequence.starts_with(&self.pending) {
+                if sequence.len() == self.pending.len() {
+                    self.pending.clear();
+                    return SequenceResult::Resolved(operation.clone());
+                }
+                is_prefix = true;
+            }
+        }
+        if is_prefix {
+            SequenceResult::Pending
+        } else {
+            self.pending.pop();
+            SequenceResult::Invalid
+        }
+    }
+
+    /// Create a `Behavior` that feeds events to this `KeySequence` and calls `on_resolved` if a
+    /// sequence is complete.
+    ///
+    /// Events that are part of a (pending or resolved) sequence are consumed. If the event neither
+    /// continues the pending sequence nor starts a new one, it is passed on. Pending events that
+    /// are discarded in the process can be retrieved via `discarded`.
+    pub fn behavior<'a, F: FnOnce(O)>(
+        &'a mut self,
+        on_resolved: F,
+    ) -> KeySequenceBehavior<'a, O, F> {
+        KeySequenceBehavior {
+            sequence: self,
+            on_resolved,
+        }
+    }
+}
+
+/// A `Behavior` that feeds input to a `KeySequence`. See `KeySequence::behavior`.
+pub struct KeySequenceBehavior<'a, O: 'a, F: FnOnce(O)> {
+    sequence: &'a mut KeySequence<O>,
+    on_resolved: F,
+}
+
+impl<'a, O: Clone + 'a, F: FnOnce(O)> Behavior for KeySequenceBehavior<'a, O, F> {
+    fn input(self, input: Input) -> Option<Input> {
+        match self.sequence.feed(input.event.clone()) {
+            SequenceResult::Pending => None,
+            SequenceResult::Resolved(operation) => {
+                (self.on_resolved)(operation);
+                None
+            }
+            SequenceResult::Invalid => Some(input),
+        }
+    }
+}
@@ -0,0 +1,106 @@
+//! A horizontal line with an optional, centered title.
+use base::basic_types::*;
+use base::{Cursor, GraphemeCluster, StyleModifier, Window};
+use widget::{text_width, Demand, Demand2D, RenderingHints, Widget};
+
+/// A horizontal line spanning the whole width of the window with an optional, centered title, e.g.,
+/// `──── Settings ────`.
+///
+/// This is useful as a section header between widgets in a `VLayout`.
+///
+/// # Examples:
+/// ```
+/// use unsegen::base::StyleModifier;
+/// use unsegen::widget::builtin::HorizontalRule;
+///
+/// let rule = HorizontalRule::new()
+///     .title("Settings")
+///     .title_style(StyleModifier::new().bold(true));
+/// ```
+pub struct HorizontalRule {
+    title: String,
+    glyph: GraphemeCluster,
+    title_style: StyleModifier,
+}
+
+impl HorizontalRule {
+    /// Create a horizontal rule without a title that is drawn using '─'.
+    pub fn new() -> Self {
+        HorizontalRule {
+            title: String::new(),
+            glyph: GraphemeCluster::try_from('─').unwrap(),
+            title_style: StyleModifier::new(),
+        }
+    }
+
+    /// Specify the title that will be drawn (padded by a space on each side) in the center of the
+    /// line. (Default: no title)
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Specify the grapheme cluster that the line is drawn with. (Default: '─')
+    pub fn glyph(mut self, glyph: GraphemeCluster) -> Self {
+        self.glyph = glyph;
+        self
+    }
+
+    /// Specify the style that the title is drawn with. (Default: no change)
+    pub fn title_style(mut self, style: StyleModifier) -> Self {
+        self.title_style = style;
+        self
+    }
+
+    fn padded_title_width(&self) -> Width {
+        if self.title.is_empty() {
+            Width::new(0).unwrap()
+        } else {
+            text_width(&self.title) + 2
+        }
+    }
+}
+
+impl Widget for HorizontalRule {
+    fn space_demand(&self) -> Demand2D {
+        Demand2D {
+            width: Demand::at_least(self.padded_title_width()),
+            height: Demand::exact(1),
+        }
+    }
+    fn draw(&self, mut window: Window, _hints: RenderingHints) {
+        window.fill(self.glyph.clone());
+        if self.title.is_empty() {
+            return;
+        }
+        let start_col = ((window.get_width() - self.padded_title_width()) / 2)
+            .from_origin()
+            .positive_or_zero();
+        let mut cursor = Cursor::new(&mut window).position(start_col, RowIndex::new(0));
+        cursor.write(" ");
+        {
+            let mut cursor = cursor.save().style_modifier();
+            cursor.apply_style_modifier(self.title_style);
+            cursor.write(&self.title);
+        }
+        cursor.write(" ");
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use base::test::FakeTerminal;
+
+    #[test]
+    fn test_horizontal_rule() {
+        let rule = HorizontalRule::new()
+            .glyph(GraphemeCluster::try_from('-').unwrap())
+            .title("ab");
+        assert_eq!(rule.space_demand().width, Demand::at_least(4));
+
+        let mut term = FakeTerminal::with_size((8, 1));
+        rule.draw(term.create_root_window(), RenderingHints::default());
+        term.assert_looks_like("-- ab --");
+    }
+}
@@ -0,0 +1,139 @@
+//! A (possibly long) text that is word-wrapped across multiple lines.
+use base::basic_types::*;
+use base::{Cursor, Window};
+use unicode_segmentation::UnicodeSegmentation;
+use widget::{text_width, Demand, Demand2D, RenderingHints, Widget};
+
+/// A text that is wrapped at whitespace to fit into the width of the window.
+///
+/// Words that are wider than the window are broken (at grapheme cluster boundaries) wherever
+/// necessary. Line breaks in the text are preserved. If the window is not high enough to show all
+/// lines, the remaining lines are not drawn.
+///
+/// Use `LineLabel` for text that should never span more than a single line.
+///
+/// # Examples:
+/// ```
+/// use unsegen::base::Width;
+/// use unsegen::widget::builtin::Label;
+///
+/// let label = Label::new("the quick brown fox");
+/// assert_eq!(
+///     label.lines(Width::new(10).unwrap()),
+///     vec!["the quick", "brown fox"]
+/// );
+/// ```
+pub struct Label {
+    text: String,
+}
+
+impl Label {
+    /// Create a label from the given text.
+    pub fn new(text: impl Into<String>) -> Self {
+        Label { text: text.into() }
+    }
+
+    /// Get the (unwrapped) text of the label.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Replace the text of the label.
+    pub fn set(&mut self, text: impl Into<String>) {
+        self.text = text.into();
+    }
+
+    /// Get the lines that the text is broken into if drawn in a window of the given width.
+    pub fn lines(&self, width: Width) -> Vec<String> {
+        let width = width.raw_value();
+        let mut lines = Vec::new();
+        if width == 0 {
+            return lines;
+        }
+        for paragraph in self.text.lines() {
+            let mut line = String::new();
+            let mut line_width = 0;
+            for word in paragraph.split_whitespace() {
+                let word_width = text_width(word).raw_value();
+                if !line.is_empty() {
+                    if line_width + 1 + word_width <= width {
+                        line.push(' ');
+                        line.push_str(word);
+                        line_width += 1 + word_width;
+                        continue;
+                    }
+                    lines.push(::std::mem::take(&mut line));
+                    line_width = 0;
+                }
+                // The word starts a new line, but may still have to be broken (possibly several
+                // times) if it is too wide.
+                for grapheme in word.graphemes(true) {
+                    let grapheme_width = text_width(grapheme).raw_value();
+                    if !line.is_empty() && line_width + grapheme_width > width {
+                        lines.push(::std::mem::take(&mut line));
+                        line_width = 0;
+                    }
+                    line.push_str(grapheme);
+                    line_width += grapheme_width;
+                }
+            }
+            lines.push(line);
+        }
+        lines
+    }
+}
+
+impl Widget for Label {
+    fn space_demand(&self) -> Demand2D {
+        let min_width = self
+            .text
+            .graphemes(true)
+            .map(text_width)
+            .max()
+            .unwrap_or(Width::new(0).unwrap());
+        Demand2D {
+            width: Demand::at_least(min_width),
+            // The actual number of lines depends on the width of the window.
+            height: Demand::at_least(1),
+        }
+    }
+    fn draw(&self, mut window: Window, _hints: RenderingHints) {
+        let lines = self.lines(window.get_width());
+        let mut cursor = Cursor::new(&mut window);
+        for (row, line) in lines.iter().enumerate() {
+            cursor.move_to(ColIndex::new(0), RowIndex::new(row as i32));
+            cursor.write(line);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use base::test::FakeTerminal;
+
+    fn draw(label: &Label, size: (u32, u32)) -> FakeTerminal {
+        let mut term = FakeTerminal::with_size(size);
+        label.draw(term.create_root_window(), RenderingHints::default());
+        term
+    }
+
+    #[test]
+    fn test_wrap_words() {
+        let label = Label::new("the quick brown fox");
+        draw(&label, (5, 4)).assert_looks_like("the  |quick|brown|fox  ");
+        draw(&label, (5, 2)).assert_looks_like("the  |quick");
+        draw(&label, (9, 3)).assert_looks_like("the quick|brown fox|         ");
+    }
+
+    #[test]
+    fn test_wrap_long_words() {
+        let label = Label::new("ab cdefghijk\n漢字漢 x");
+        assert_eq!(
+            label.lines(Width::new(5).unwrap()),
+            vec!["ab", "cdefg", "hijk", "漢字", "漢 x"]
+        );
+        assert_eq!(label.space_demand().width, Demand::at_least(2));
+        assert!(label.lines(Width::new(0).unwrap()).is_empty());
+    }
+}
@@ -1,19 +1,104 @@
 //! A user-editable line of text.
 use base::basic_types::*;
-use base::{BoolModifyMode, Cursor, StyleModifier, Window};
+use base::{BoolModifyMode, Color, Cursor, StyleModifier, Window};
 use input::{Editable, Navigatable, OperationResult, Writable};
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::ops::Range;
 use unicode_segmentation::UnicodeSegmentation;
 use widget::{
-    count_grapheme_clusters, text_width, Blink, Demand, Demand2D, RenderingHints, Widget,
+    byte_offset_to_grapheme_index, count_grapheme_clusters, grapheme_index_to_byte_offset,
+    text_width, visible_byte_range, Blink, Demand, Demand2D, RenderingHints, Widget,
 };
 
+/// Notifications that a `LineEdit` emits to the host application.
+///
+/// Widgets do not store callbacks. Instead, mutating methods return events which the host can
+/// handle directly or collect in a sink (e.g., a `Vec`) while input is processed and handle later.
+///
+/// # Examples:
+/// ```
+/// use unsegen::input::*;
+/// use unsegen::widget::builtin::{LineEdit, LineEditEvent};
+///
+/// let mut edit = LineEdit::new();
+/// let mut events = Vec::new();
+///
+/// for input in Input::read_all(&b"hi\r"[..]) {
+///     input
+///         .unwrap()
+///         .chain((Key::Char('\n'), || events.push(edit.submit())))
+///         .chain(EditBehavior::new(&mut edit))
+///         .finish();
+/// }
+///
+/// assert_eq!(events, vec![LineEditEvent::Submitted("hi".to_owned())]);
+/// assert_eq!(edit.get(), "");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LineEditEvent {
+    /// The line was submitted (e.g., by pressing enter) with the contained text.
+    Submitted(String),
+}
+
 /// A user-editable line of text.
 ///
 /// In addition to the current text, the `LineEdit` has a concept of a cursor whose position can
 /// change, but is always on a grapheme cluster in the current text.
+///
+/// Additional cursors can be added using `add_cursor`. Text that is written or deleted via the
+/// `Writable` and `Editable` implementations is then inserted/erased at every cursor.
 pub struct LineEdit {
     text: String,
     cursor_pos: usize,
+    additional_cursors: Vec<usize>,
+    selection_anchor: Option<usize>,
+    max_display_width: Option<Width>,
+    tab_width: Width,
+    max_length: Option<usize>,
+    validator: Option<Validator>,
+    content_changed: bool,
+    remote_cursors: Vec<(usize, StyleModifier)>,
+    clear_requires_confirmation: bool,
+    clear_armed: bool,
+    bracket_matching: bool,
+    prompt: String,
+    placeholder: String,
+    kill_buffer: String,
+    last_kill: Option<LastKill>,
+    scroll_offset: Cell<u32>,
+    undo_stack: VecDeque<UndoState>,
+    redo_stack: Vec<UndoState>,
+    undo_depth: usize,
+    typed_state: Option<UndoState>,
+    continue_undo_group: bool,
+    history: VecDeque<String>,
+    history_limit: usize,
+    history_pos: Option<usize>,
+    history_stash: String,
+}
+
+/// Decides whether the prospective content of a `LineEdit` is acceptable. See `set_validator`.
+type Validator = Box<dyn Fn(&str) -> bool>;
+
+/// A snapshot of the content of a `LineEdit` that can be restored via `undo`/`redo`.
+#[derive(Clone, PartialEq)]
+struct UndoState {
+    text: String,
+    cursor_pos: usize,
+}
+
+/// State after the most recent kill, used to detect successive kills.
+struct LastKill {
+    direction: KillDirection,
+    text: String,
+    cursor_pos: usize,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum KillDirection {
+    Forwards,
+    Backwards,
 }
 
 impl LineEdit {
@@ -22,6 +107,54 @@ impl LineEdit {
         LineEdit {
             text: String::new(),
             cursor_pos: 0,
+            additional_cursors: Vec::new(),
+            selection_anchor: None,
+            max_display_width: None,
+            tab_width: Width::new(8).unwrap(),
+            max_length: None,
+            validator: None,
+            content_changed: false,
+            remote_cursors: Vec::new(),
+            clear_requires_confirmation: false,
+            clear_armed: false,
+            bracket_matching: false,
+            prompt: String::new(),
+            placeholder: String::new(),
+            kill_buffer: String::new(),
+            last_kill: None,
+            scroll_offset: Cell::new(0),
+            undo_stack: VecDeque::new(),
+            redo_stack: Vec::new(),
+            undo_depth: 100,
+            typed_state: None,
+            continue_undo_group: false,
+            history: VecDeque::new(),
+            history_limit: 100,
+            history_pos: None,
+            history_stash: String::new(),
+        }
+    }
+
+    /// Create a LineEdit with initial content and the cursor placed at the specified grapheme
+    /// cluster position, which is clamped to the end of the text.
+    ///
+    /// # Examples:
+    /// ```
+    /// use unsegen::widget::builtin::LineEdit;
+    ///
+    /// let l = LineEdit::with_text("fööbar.txt", 6);
+    /// assert_eq!(l.cursor_pos(), 8); // fööbar|.txt (byte position)
+    ///
+    /// let l = LineEdit::with_text("föö", 10);
+    /// assert_eq!(l.cursor_pos(), 5); // föö|
+    /// ```
+    pub fn with_text(text: impl Into<String>, cursor: usize) -> Self {
+        let text = text.into();
+        let cursor_pos = ::std::cmp::min(cursor, count_grapheme_clusters(&text));
+        LineEdit {
+            text,
+            cursor_pos,
+            ..Self::new()
         }
     }
 
@@ -32,62 +165,747 @@ impl LineEdit {
 
     /// Set (and overwrite) the current content. The cursor will be placed at the very end of the
     /// line.
+    ///
+    /// All additional cursors are removed.
     pub fn set(&mut self, text: impl Into<String>) {
-        self.text = text.into();
+        let text = text.into();
+        if text != self.text {
+            self.save_undo_state();
+            self.text = text;
+            self.content_changed = true;
+        }
         self.move_cursor_to_end_of_line();
     }
 
+    /// Mark the current content as "submitted", e.g., if the user has pressed enter.
+    ///
+    /// The line is cleared and its former content is returned as a `LineEditEvent::Submitted`.
+    pub fn submit(&mut self) -> LineEditEvent {
+        if !self.text.is_empty() {
+            self.save_undo_state();
+            self.content_changed = true;
+        }
+        let text = ::std::mem::take(&mut self.text);
+        self.cursor_pos = 0;
+        self.additional_cursors.clear();
+        LineEditEvent::Submitted(text)
+    }
+
+    fn undo_state(&self) -> UndoState {
+        UndoState {
+            text: self.text.clone(),
+            cursor_pos: self.cursor_pos,
+        }
+    }
+
+    /// Record the current state before the content is changed.
+    ///
+    /// As any change of the content invalidates the selection, it is removed as well.
+    fn save_undo_state(&mut self) {
+        self.selection_anchor = None;
+        if !self.continue_undo_group {
+            let state = self.undo_state();
+            self.undo_stack.push_back(state);
+            if self.undo_stack.len() > self.undo_depth {
+                self.undo_stack.pop_front();
+            }
+        }
+        self.redo_stack.clear();
+        self.typed_state = None;
+    }
+
+    fn restore(&mut self, state: UndoState) {
+        self.text = state.text;
+        self.cursor_pos = state.cursor_pos;
+        self.additional_cursors.clear();
+        self.selection_anchor = None;
+        self.content_changed = true;
+        self.typed_state = None;
+    }
+
+    /// Revert the most recent change of the content (and restore the cursor position before the
+    /// change). Consecutively typed characters are reverted as a whole.
+    ///
+    /// # Examples:
+    /// ```
+    /// use unsegen::input::{Editable, Writable};
+    /// use unsegen::widget::builtin::LineEdit;
+    ///
+    /// let mut l = LineEdit::new();
+    /// l.write('a').unwrap();
+    /// l.write('b').unwrap();
+    /// l.delete_backwards().unwrap();
+    /// l.undo().unwrap();
+    /// assert_eq!(l.get(), "ab");
+    /// l.undo().unwrap();
+    /// assert_eq!(l.get(), "");
+    /// assert!(!l.can_undo());
+    /// l.redo().unwrap();
+    /// assert_eq!(l.get(), "ab");
+    /// ```
+    pub fn undo(&mut self) -> OperationResult {
+        let state = self.undo_stack.pop_back().ok_or(())?;
+        let current = self.undo_state();
+        self.redo_stack.push(current);
+        self.restore(state);
+        Ok(())
+    }
+
+    /// Reapply the most recently undone change. See `undo`.
+    pub fn redo(&mut self) -> OperationResult {
+        let state = self.redo_stack.pop().ok_or(())?;
+        let current = self.undo_state();
+        self.undo_stack.push_back(current);
+        self.restore(state);
+        Ok(())
+    }
+
+    /// Check whether there is a change that can be reverted using `undo`.
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    /// Check whether there is a change that can be reapplied using `redo`.
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    /// Limit the number of changes that can be reverted using `undo`. Older changes are
+    /// forgotten. (Default: 100)
+    pub fn set_undo_depth(&mut self, depth: usize) {
+        self.undo_depth = depth;
+        while self.undo_stack.len() > depth {
+            self.undo_stack.pop_front();
+        }
+    }
+
+    /// Add an entry (e.g., a submitted line) to the input history. Entries equal to the most
+    /// recent one are ignored. Navigating the history starts again from the most recent entry.
+    ///
+    /// # Examples:
+    /// ```
+    /// use unsegen::input::Writable;
+    /// use unsegen::widget::builtin::LineEdit;
+    ///
+    /// let mut l = LineEdit::new();
+    /// l.push_history("first".to_owned());
+    /// l.push_history("second".to_owned());
+    /// l.write('x').unwrap();
+    ///
+    /// l.history_prev().unwrap();
+    /// assert_eq!(l.get(), "second");
+    /// l.history_prev().unwrap();
+    /// assert_eq!(l.get(), "first");
+    /// assert!(l.history_prev().is_err());
+    ///
+    /// l.history_next().unwrap();
+    /// l.history_next().unwrap();
+    /// assert_eq!(l.get(), "x");
+    /// assert!(l.history_next().is_err());
+    /// ```
+    pub fn push_history(&mut self, entry: String) {
+        if self.history.back() != Some(&entry) {
+            self.history.push_back(entry);
+            if self.history.len() > self.history_limit {
+                self.history.pop_front();
+            }
+        }
+        self.history_pos = None;
+    }
+
+    /// Replace the current text with the previous (i.e., older) history entry and move the cursor
+    /// to the end. When leaving the current line, it is stashed so that it can be restored using
+    /// `history_next`.
+    pub fn history_prev(&mut self) -> OperationResult {
+        let pos = match self.history_pos {
+            Some(0) => return Err(()),
+            Some(pos) => pos - 1,
+            None if self.history.is_empty() => return Err(()),
+            None => {
+                self.history_stash = self.text.clone();
+                self.history.len() - 1
+            }
+        };
+        self.history_pos = Some(pos);
+        let entry = self.history[pos].clone();
+        self.set(entry);
+        Ok(())
+    }
+
+    /// Replace the current text with the next (i.e., newer) history entry (or the stashed line
+    /// after the most recent entry) and move the cursor to the end.
+    pub fn history_next(&mut self) -> OperationResult {
+        let pos = self.history_pos.ok_or(())?;
+        let entry = if pos + 1 < self.history.len() {
+            self.history_pos = Some(pos + 1);
+            self.history[pos + 1].clone()
+        } else {
+            self.history_pos = None;
+            ::std::mem::take(&mut self.history_stash)
+        };
+        self.set(entry);
+        Ok(())
+    }
+
+    /// Limit the number of entries kept in the input history. Older entries are forgotten.
+    /// (Default: 100)
+    pub fn set_history_limit(&mut self, limit: usize) {
+        self.history_limit = limit;
+        while self.history.len() > limit {
+            self.history.pop_front();
+        }
+        self.history_pos = None;
+    }
+
+    /// Check whether the content (in contrast to only the cursor position) has changed since the
+    /// last call of this method and reset the flag.
+    ///
+    /// This is useful to avoid redundant work (e.g., filtering based on the current text) after
+    /// passing input to the `LineEdit`.
+    ///
+    /// # Examples:
+    /// ```
+    /// use unsegen::input::{Navigatable, Writable};
+    /// use unsegen::widget::builtin::LineEdit;
+    ///
+    /// let mut l = LineEdit::new();
+    /// l.write('a').unwrap();
+    /// assert!(l.take_content_changed());
+    /// assert!(!l.take_content_changed());
+    /// l.move_left().unwrap();
+    /// assert!(!l.take_content_changed());
+    /// ```
+    pub fn take_content_changed(&mut self) -> bool {
+        ::std::mem::replace(&mut self.content_changed, false)
+    }
+
     /// Move the cursor to the end, i.e., *behind* the last grapheme cluster.
+    ///
+    /// All additional cursors are removed.
     pub fn move_cursor_to_end_of_line(&mut self) {
         self.cursor_pos = count_grapheme_clusters(&self.text) as usize;
+        self.additional_cursors.clear();
+        self.selection_anchor = None;
     }
 
     /// Move the cursor to the beginning, i.e., *onto* the first grapheme cluster.
+    ///
+    /// All additional cursors are removed.
     pub fn move_cursor_to_beginning_of_line(&mut self) {
         self.cursor_pos = 0;
+        self.additional_cursors.clear();
+        self.selection_anchor = None;
     }
 
     /// Move the cursor one grapheme cluster to the right if possible.
+    ///
+    /// Additional cursors are moved as well (if possible).
     pub fn move_cursor_right(&mut self) -> Result<(), ()> {
+        self.selection_anchor = None;
+        let num_graphemes = count_grapheme_clusters(&self.text);
+        for pos in self.additional_cursors.iter_mut() {
+            *pos = ::std::cmp::min(*pos + 1, num_graphemes);
+        }
         let new_pos = self.cursor_pos + 1;
-        if new_pos <= count_grapheme_clusters(&self.text) as usize {
+        let res = if new_pos <= num_graphemes {
             self.cursor_pos = new_pos;
             Ok(())
         } else {
             Err(())
-        }
+        };
+        self.normalize_additional_cursors();
+        res
     }
 
     /// Move the cursor one grapheme cluster to the left if possible.
+    ///
+    /// Additional cursors are moved as well (if possible).
     pub fn move_cursor_left(&mut self) -> Result<(), ()> {
-        if self.cursor_pos > 0 {
+        self.selection_anchor = None;
+        for pos in self.additional_cursors.iter_mut() {
+            *pos = pos.saturating_sub(1);
+        }
+        let res = if self.cursor_pos > 0 {
             self.cursor_pos -= 1;
             Ok(())
         } else {
             Err(())
+        };
+        self.normalize_additional_cursors();
+        res
+    }
+
+    /// Add another cursor at the specified grapheme cluster position.
+    ///
+    /// An error is returned if the position is beyond the end of the text or if there already is
+    /// a cursor at the position.
+    ///
+    /// # Examples:
+    /// ```
+    /// use unsegen::input::Writable;
+    /// use unsegen::widget::builtin::LineEdit;
+    ///
+    /// let mut l = LineEdit::new();
+    /// l.set("ab");
+    /// assert!(l.add_cursor(0).is_ok());
+    /// assert!(l.add_cursor(3).is_err());
+    /// l.write('x').unwrap();
+    /// assert_eq!(l.get(), "xabx");
+    /// ```
+    pub fn add_cursor(&mut self, pos: usize) -> Result<(), ()> {
+        if pos > count_grapheme_clusters(&self.text) || self.cursors().contains(&pos) {
+            return Err(());
+        }
+        self.additional_cursors.push(pos);
+        self.normalize_additional_cursors();
+        Ok(())
+    }
+
+    /// Remove all but the main cursor.
+    pub fn clear_additional_cursors(&mut self) {
+        self.additional_cursors.clear();
+    }
+
+    /// Get the grapheme cluster positions of all cursors (including the main cursor) in
+    /// ascending order.
+    pub fn cursors(&self) -> Vec<usize> {
+        let mut cursors = self.additional_cursors.clone();
+        cursors.push(self.cursor_pos);
+        cursors.sort();
+        cursors
+    }
+
+    /// Display markers at the specified grapheme cluster positions in the specified styles, e.g.,
+    /// to visualize the cursors of other users in a collaborative editing session.
+    ///
+    /// In contrast to the cursors added using `add_cursor`, remote cursors are only drawn and do
+    /// not affect editing operations. They are not moved when the text changes, and positions
+    /// behind the end of the text are drawn at the end. Cursors of this `LineEdit` take precedence
+    /// when drawing.
+    pub fn set_remote_cursors(&mut self, cursors: Vec<(usize, StyleModifier)>) {
+        self.remote_cursors = cursors;
+    }
+
+    /// Get the positions and styles of all remote cursors. See `set_remote_cursors`.
+    pub fn remote_cursors(&self) -> &[(usize, StyleModifier)] {
+        &self.remote_cursors
+    }
+
+    /// Sort additional cursors and remove duplicates (also of the main cursor).
+    fn normalize_additional_cursors(&mut self) {
+        self.additional_cursors.sort();
+        self.additional_cursors.dedup();
+        let main = self.cursor_pos;
+        self.additional_cursors.retain(|&pos| pos != main);
+    }
+
+    /// Limit the (monospace) width of the text that can be entered via `insert` or `write`.
+    /// Input that would make the text wider is refused. (Default: None, i.e., unlimited)
+    ///
+    /// Note that text set via `set` is not affected and that an additional cell is required to
+    /// draw the cursor if it is at the end of the line.
+    ///
+    /// # Examples:
+    /// ```
+    /// use unsegen::base::Width;
+    /// use unsegen::input::Writable;
+    /// use unsegen::widget::builtin::LineEdit;
+    ///
+    /// let mut l = LineEdit::new();
+    /// l.set_max_display_width(Some(Width::new(3).unwrap()));
+    /// assert!(l.write('a').is_ok());
+    /// assert!(l.write('山').is_ok());
+    /// assert!(l.write('b').is_err());
+    /// assert_eq!(l.get(), "a山");
+    /// ```
+    pub fn set_max_display_width(&mut self, width: Option<Width>) {
+        self.max_display_width = width;
+    }
+
+    /// Get the maximum width of the text that can be entered. See `set_max_display_width`.
+    pub fn max_display_width(&self) -> Option<Width> {
+        self.max_display_width
+    }
+
+    /// Set the distance between tab stops. A tab character is drawn as the number of spaces that
+    /// are required to reach the next tab stop, counting columns from the beginning of the text
+    /// (i.e., excluding the prompt). (Default: 8)
+    ///
+    /// This only affects the display (and width limit): `get` still returns the raw tab character.
+    ///
+    /// # Examples:
+    /// ```
+    /// use unsegen::base::Width;
+    /// use unsegen::widget::builtin::LineEdit;
+    ///
+    /// let mut l = LineEdit::new();
+    /// l.set_tab_width(Width::new(4).unwrap());
+    /// l.set("a\tb");
+    /// assert_eq!(l.get(), "a\tb");
+    /// ```
+    pub fn set_tab_width(&mut self, width: Width) {
+        self.tab_width = width;
+    }
+
+    /// Get the distance between tab stops. See `set_tab_width`.
+    pub fn tab_width(&self) -> Width {
+        self.tab_width
+    }
+
+    /// Get the display width of a grapheme cluster that starts at column `col` of the text, taking
+    /// tab stops into account.
+    fn grapheme_width(&self, grapheme: &str, col: i32) -> i32 {
+        if grapheme == "\t" {
+            let tab_width = self.tab_width.raw_value();
+            if tab_width > 0 {
+                tab_width - col % tab_width
+            } else {
+                0
+            }
+        } else {
+            text_width(grapheme).raw_value()
         }
     }
 
-    /// Insert text directly *before* the current cursor position
-    pub fn insert(&mut self, text: &str) {
-        self.text = {
-            let grapheme_iter = self.text.graphemes(true);
-            grapheme_iter
-                .clone()
-                .take(self.cursor_pos)
-                .chain(Some(text))
-                .chain(grapheme_iter.skip(self.cursor_pos))
-                .collect()
-        };
+    /// Get the display width of `text` (assumed to start at the beginning of the line), taking
+    /// tab stops into account.
+    fn display_width(&self, text: &str) -> i32 {
+        text.graphemes(true)
+            .fold(0, |col, grapheme| col + self.grapheme_width(grapheme, col))
+    }
+
+    /// Limit the number of grapheme clusters of the text that can be entered via `insert` or
+    /// `write`. Input that would make the text longer is refused. (Default: None, i.e., unlimited)
+    ///
+    /// Note that text set via `set` is not affected.
+    ///
+    /// # Examples:
+    /// ```
+    /// use unsegen::input::Writable;
+    /// use unsegen::widget::builtin::LineEdit;
+    ///
+    /// let mut l = LineEdit::new();
+    /// l.set_max_length(Some(2));
+    /// assert!(l.write('d').is_ok());
+    /// assert!(l.write('🇪').is_ok());
+    /// assert!(l.write('🇺').is_ok()); // Forms a single grapheme cluster with the previous char
+    /// assert!(l.write('x').is_err());
+    /// assert_eq!(l.get(), "d🇪🇺");
+    /// ```
+    pub fn set_max_length(&mut self, length: Option<usize>) {
+        self.max_length = length;
+    }
+
+    /// Get the maximum number of grapheme clusters that can be entered. See `set_max_length`.
+    pub fn max_length(&self) -> Option<usize> {
+        self.max_length
+    }
+
+    /// Specify a function that decides whether text entered via `insert` or `write` is
+    /// acceptable. It receives the prospective new content and the input is refused if it returns
+    /// false.
+    ///
+    /// The validator is not consulted when text is deleted (e.g., via `delete_backwards` or
+    /// `clear`), so that the user can always recover from an invalid state, nor for text set via
+    /// `set`.
+    ///
+    /// # Examples:
+    /// ```
+    /// use unsegen::input::Writable;
+    /// use unsegen::widget::builtin::LineEdit;
+    ///
+    /// let mut l = LineEdit::new();
+    /// l.set_validator(Box::new(|text| text.chars().all(|c| c.is_ascii_digit())));
+    /// assert!(l.write('4').is_ok());
+    /// assert!(l.write('x').is_err());
+    /// assert!(l.write('2').is_ok());
+    /// assert_eq!(l.get(), "42");
+    /// ```
+    pub fn set_validator(&mut self, validator: Box<dyn Fn(&str) -> bool>) {
+        self.validator = Some(validator);
+    }
+
+    /// Check whether the validator (if any) accepts `new_text`. See `set_validator`.
+    fn is_valid(&self, new_text: &str) -> bool {
+        match self.validator {
+            Some(ref validator) => validator(new_text),
+            None => true,
+        }
+    }
+
+    /// Check whether replacing the current text with `new_text` would violate the maximum width or
+    /// length. Changes that do not grow the text (e.g., deletions) are always allowed, even if the
+    /// current text (set via `set`) already exceeds a limit.
+    fn exceeds_limits(&self, new_text: &str) -> bool {
+        let too_wide = match self.max_display_width {
+            Some(max_width) => {
+                let width = self.display_width(new_text);
+                width > max_width.raw_value() && width > self.display_width(&self.text)
+            }
+            None => false,
+        };
+        let too_long = match self.max_length {
+            Some(max_length) => {
+                let length = count_grapheme_clusters(new_text);
+                length > max_length && length > count_grapheme_clusters(&self.text)
+            }
+            None => false,
+        };
+        too_wide || too_long
+    }
+
+    /// Extend the selection (or start a new one at the cursor) by moving the cursor one grapheme
+    /// cluster to the left.
+    ///
+    /// All additional cursors are removed.
+    pub fn select_left(&mut self) -> OperationResult {
+        let anchor = self.selection_anchor.unwrap_or(self.cursor_pos);
+        self.additional_cursors.clear();
+        let res = self.move_cursor_left();
+        self.selection_anchor = Some(anchor);
+        res
+    }
+
+    /// Extend the selection (or start a new one at the cursor) by moving the cursor one grapheme
+    /// cluster to the right.
+    ///
+    /// All additional cursors are removed.
+    pub fn select_right(&mut self) -> OperationResult {
+        let anchor = self.selection_anchor.unwrap_or(self.cursor_pos);
+        self.additional_cursors.clear();
+        let res = self.move_cursor_right();
+        self.selection_anchor = Some(anchor);
+        res
+    }
+
+    /// Select the whole text and move the cursor to the end.
+    ///
+    /// # Examples:
+    /// ```
+    /// use unsegen::input::Writable;
+    /// use unsegen::widget::builtin::LineEdit;
+    ///
+    /// let mut l = LineEdit::new();
+    /// l.set("foo bar");
+    /// l.select_all();
+    /// assert_eq!(l.selected_text(), Some("foo bar"));
+    /// l.write('x').unwrap();
+    /// assert_eq!(l.get(), "x");
+    /// assert_eq!(l.selected_text(), None);
+    /// ```
+    pub fn select_all(&mut self) {
+        self.move_cursor_to_end_of_line();
+        self.selection_anchor = Some(0);
+    }
+
+    /// Get the grapheme cluster range between the selection anchor and the cursor if it is not
+    /// empty.
+    fn selection_range(&self) -> Option<Range<usize>> {
+        let anchor = self.selection_anchor?;
+        let start = ::std::cmp::min(anchor, self.cursor_pos);
+        let end = ::std::cmp::max(anchor, self.cursor_pos);
+        if start < end {
+            Some(start..end)
+        } else {
+            None
+        }
+    }
+
+    /// Get the byte range of the selection in the current text (obtainable by `get`).
+    fn selection_byte_range(&self) -> Option<Range<usize>> {
+        let range = self.selection_range()?;
+        let to_byte = |i| grapheme_index_to_byte_offset(&self.text, i).unwrap_or(self.text.len());
+        Some(to_byte(range.start)..to_byte(range.end))
+    }
+
+    /// Get the currently selected text, if any.
+    pub fn selected_text(&self) -> Option<&str> {
+        self.selection_byte_range().map(|range| &self.text[range])
+    }
+
+    /// Replace the selected text and place the cursor behind the replacement.
+    fn replace_selection(&mut self, replacement: &str) -> OperationResult {
+        let range = self.selection_byte_range().ok_or(())?;
+        let end = self.replace_range(range, replacement)?;
+        self.cursor_pos = count_grapheme_clusters(&self.text[..end]);
+        self.additional_cursors.clear();
+        self.selection_anchor = None;
+        Ok(())
+    }
+
+    /// Specify whether `Editable::clear` has to be confirmed by a preceding call to `arm_clear`.
+    /// If so, `clear` fails and leaves the text unchanged unless it is armed. This protects fields
+    /// that are rarely cleared from accidental data loss. (Default: false)
+    ///
+    /// # Examples:
+    /// ```
+    /// use unsegen::input::Editable;
+    /// use unsegen::widget::builtin::LineEdit;
+    ///
+    /// let mut l = LineEdit::new();
+    /// l.set("important");
+    /// l.set_clear_requires_confirmation(true);
+    /// assert!(l.clear().is_err());
+    /// assert_eq!(l.get(), "important");
+    ///
+    /// l.arm_clear();
+    /// assert!(l.clear().is_ok());
+    /// assert_eq!(l.get(), "");
+    /// ```
+    pub fn set_clear_requires_confirmation(&mut self, requires_confirmation: bool) {
+        self.clear_requires_confirmation = requires_confirmation;
+        self.clear_armed = false;
+    }
+
+    /// Allow the next call of `Editable::clear` to proceed if `set_clear_requires_confirmation` is
+    /// enabled. The next call of `clear` disarms it again, regardless of its outcome.
+    pub fn arm_clear(&mut self) {
+        self.clear_armed = true;
+    }
+
+    /// Specify a non-editable prompt (e.g., `/` or `> `) that is drawn before the text. The prompt
+    /// is not part of the content returned by `get` and the cursor never moves onto it.
+    /// (Default: empty)
+    ///
+    /// # Examples:
+    /// ```
+    /// # use unsegen::base::terminal::test::FakeTerminal;
+    /// use unsegen::input::Writable;
+    /// use unsegen::widget::*;
+    /// use unsegen::widget::builtin::LineEdit;
+    ///
+    /// let mut l = LineEdit::new();
+    /// l.set_prompt("> ");
+    /// l.write('a').unwrap();
+    /// assert_eq!(l.get(), "a");
+    ///
+    /// let mut term = FakeTerminal::with_size((5, 1));
+    /// l.as_widget().draw(term.create_root_window(), RenderingHints::new().active(false));
+    /// term.assert_looks_like("> a  ");
+    /// ```
+    pub fn set_prompt(&mut self, prompt: impl Into<String>) {
+        self.prompt = prompt.into();
+    }
+
+    /// Get the prompt that is drawn before the text. See `set_prompt`.
+    pub fn prompt(&self) -> &str {
+        &self.prompt
+    }
+
+    /// Specify a placeholder text (e.g., `search…`) that is drawn instead of the (empty) content
+    /// if the `LineEdit` is empty and inactive. The placeholder is not part of the content
+    /// returned by `get`. See `LineEditWidget::placeholder_style`. (Default: empty)
+    pub fn set_placeholder(&mut self, placeholder: impl Into<String>) {
+        self.placeholder = placeholder.into();
+    }
+
+    /// Get the placeholder text. See `set_placeholder`.
+    pub fn placeholder(&self) -> &str {
+        &self.placeholder
+    }
+
+    /// Specify whether a bracket or quote on or directly before the cursor is highlighted
+    /// together with its matching counterpart when drawing. Nesting of brackets is respected.
+    /// Delimiters without a counterpart are highlighted as errors. See
+    /// `LineEditWidget::bracket_match_style` and `LineEditWidget::bracket_mismatch_style`.
+    /// (Default: false)
+    pub fn set_bracket_matching(&mut self, enabled: bool) {
+        self.bracket_matching = enabled;
+    }
+
+    /// Find the delimiter (at the cursor or directly before it) that should be highlighted and
+    /// its matching counterpart (if any) as grapheme cluster positions.
+    fn bracket_pair(&self) -> Option<(usize, Option<usize>)> {
+        let graphemes = self.text.graphemes(true).collect::<Vec<_>>();
+        let candidates = [Some(self.cursor_pos), self.cursor_pos.checked_sub(1)];
+        candidates
+            .iter()
+            .filter_map(|&pos| pos)
+            .filter(|&pos| pos < graphemes.len())
+            .filter_map(|pos| find_matching_delimiter(&graphemes, pos).map(|m| (pos, m)))
+            .next()
+    }
+
+    /// Append text to the end of the line without moving the cursor(s).
+    ///
+    /// The text is left unchanged and an error is returned if the resulting text
+    ///
+    /// - would exceed the maximum display width (see `set_max_display_width`),
+    /// - would exceed the maximum length (see `set_max_length`), or
+    /// - is rejected by the validator (see `set_validator`).
+    ///
+    /// # Examples:
+    /// ```
+    /// use unsegen::widget::builtin::LineEdit;
+    ///
+    /// let mut l = LineEdit::new();
+    /// l.push_str("ab").unwrap();
+    /// l.push_char('c').unwrap();
+    /// assert_eq!(l.get(), "abc");
+    /// assert_eq!(l.cursor_pos(), 0);
+    ///
+    /// l.set_max_length(Some(4));
+    /// assert!(l.push_str("de").is_err());
+    /// assert_eq!(l.get(), "abc");
+    /// ```
+    pub fn push_str(&mut self, text: &str) -> Result<(), ()> {
+        let end = self.text.len();
+        self.replace_range(end..end, text).map(|_| ())
+    }
+
+    /// Append a single character to the end of the line without moving the cursor(s).
+    ///
+    /// Fails under the same conditions as `push_str`, leaving the text unchanged.
+    pub fn push_char(&mut self, c: char) -> Result<(), ()> {
+        self.push_str(c.encode_utf8(&mut [0; 4]))
+    }
+
+    /// Insert text directly *before* the current cursor position.
+    ///
+    /// If the resulting text would exceed the maximum display width, the text is left unchanged
+    /// and an error is returned.
+    pub fn insert(&mut self, text: &str) -> Result<(), ()> {
+        let cursor_pos = self.cursor_pos;
+        self.insert_at(&[cursor_pos], text)?;
+        self.cursor_pos = cursor_pos;
+        Ok(())
+    }
+
+    /// Insert text directly *before* all of the given (sorted) grapheme cluster positions.
+    ///
+    /// Cursors behind an insertion position are moved accordingly.
+    fn insert_at(&mut self, positions: &[usize], text: &str) -> Result<(), ()> {
+        let mut new_text = String::new();
+        let mut positions_iter = positions.iter().peekable();
+        for (i, grapheme) in self.text.graphemes(true).enumerate() {
+            if positions_iter.peek() == Some(&&i) {
+                positions_iter.next();
+                new_text.push_str(text);
+            }
+            new_text.push_str(grapheme);
+        }
+        if positions_iter.next().is_some() {
+            new_text.push_str(text);
+        }
+        if self.exceeds_limits(&new_text) || !self.is_valid(&new_text) {
+            return Err(());
+        }
+        self.save_undo_state();
+        self.text = new_text;
+        self.content_changed = true;
+
+        let inserted = count_grapheme_clusters(text);
+        let shift = |pos: usize| pos + inserted * positions.iter().filter(|&&p| p <= pos).count();
+        self.cursor_pos = shift(self.cursor_pos);
+        for pos in self.additional_cursors.iter_mut() {
+            *pos = shift(*pos);
+        }
+        Ok(())
     }
 
     /// Returns the byte position of the cursor in the current text (obtainable by `get`)
     pub fn cursor_pos(&self) -> usize {
-        self.text
-            .grapheme_indices(true)
-            .nth(self.cursor_pos)
-            .map(|(index, _)| index)
-            .unwrap_or_else(|| self.text.len())
+        grapheme_index_to_byte_offset(&self.text, self.cursor_pos).unwrap_or(self.text.len())
     }
 
     /// Set the cursor by specifying its position as the byte position in the displayed string.
@@ -109,40 +927,317 @@ impl LineEdit {
     /// assert!(l.set_cursor_pos(5).is_err());
     /// ```
     pub fn set_cursor_pos(&mut self, pos: usize) -> Result<(), ()> {
-        if let Some(grapheme_index) = self
+        if let Some(grapheme_index) = byte_offset_to_grapheme_index(&self.text, pos) {
+            self.cursor_pos = grapheme_index;
+            self.additional_cursors.clear();
+            self.selection_anchor = None;
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+
+    /// Returns the position of the cursor as the number of grapheme clusters in front of it.
+    pub fn cursor_grapheme_index(&self) -> usize {
+        self.cursor_pos
+    }
+
+    /// Set the cursor by specifying the number of grapheme clusters in front of it, e.g., to
+    /// position it at a mouse click or a search result.
+    ///
+    /// If the index is larger than the number of grapheme clusters in the text, an error is
+    /// returned and the cursor position is left unchanged. All additional cursors are removed.
+    ///
+    /// # Examples:
+    /// ```
+    /// use unsegen::widget::builtin::LineEdit;
+    ///
+    /// let mut l = LineEdit::new();
+    /// l.set("löl");
+    /// assert!(l.set_cursor_grapheme_index(2).is_ok()); // lö|l
+    /// assert_eq!(l.cursor_grapheme_index(), 2);
+    /// assert_eq!(l.cursor_pos(), 3);
+    /// assert!(l.set_cursor_grapheme_index(3).is_ok()); // löl|
+    /// assert!(l.set_cursor_grapheme_index(4).is_err());
+    /// assert_eq!(l.cursor_grapheme_index(), 3);
+    /// ```
+    pub fn set_cursor_grapheme_index(&mut self, index: usize) -> Result<(), ()> {
+        if index > count_grapheme_clusters(&self.text) {
+            return Err(());
+        }
+        self.cursor_pos = index;
+        self.additional_cursors.clear();
+        self.selection_anchor = None;
+        Ok(())
+    }
+
+    /// Erase the grapheme clusters at the specified (grapheme cluster) positions.
+    ///
+    /// Cursors behind an erased grapheme cluster are moved accordingly.
+    fn erase_symbols_at(&mut self, positions: &[usize]) -> Result<(), ()> {
+        let num_graphemes = count_grapheme_clusters(&self.text);
+        let positions = positions
+            .iter()
+            .cloned()
+            .filter(|&pos| pos < num_graphemes)
+            .collect::<Vec<_>>();
+        if positions.is_empty() {
+            return Err(());
+        }
+        self.save_undo_state();
+        self.content_changed = true;
+        self.text = self
             .text
-            .grapheme_indices(true)
+            .graphemes(true)
             .enumerate()
-            .find(|(_, (byte_index, _))| *byte_index == pos)
-            .map(|(grapheme_index, _)| grapheme_index)
-            .or_else(|| {
-                if pos == self.text.len() {
-                    Some(count_grapheme_clusters(&self.text))
+            .filter_map(|(i, s)| {
+                if !positions.contains(&i) {
+                    Some(s)
                 } else {
                     None
                 }
             })
+            .collect();
+
+        let shift = |pos: usize| pos - positions.iter().filter(|&&p| p < pos).count();
+        self.cursor_pos = shift(self.cursor_pos);
+        for pos in self.additional_cursors.iter_mut() {
+            *pos = shift(*pos);
+        }
+        self.normalize_additional_cursors();
+        Ok(())
+    }
+
+    /// Replace the specified byte range of the text (which must lie on grapheme cluster
+    /// boundaries) and return the byte position of the end of the replacement.
+    ///
+    /// Placing the cursors is up to the caller.
+    fn replace_range(&mut self, range: Range<usize>, replacement: &str) -> Result<usize, ()> {
+        let mut new_text = String::with_capacity(self.text.len() + replacement.len());
+        new_text.push_str(&self.text[..range.start]);
+        new_text.push_str(replacement);
+        let replacement_end = new_text.len();
+        new_text.push_str(&self.text[range.end..]);
+        if self.exceeds_limits(&new_text) || (!replacement.is_empty() && !self.is_valid(&new_text))
         {
-            self.cursor_pos = grapheme_index;
-            Ok(())
-        } else {
-            Err(())
+            return Err(());
+        }
+        if new_text != self.text {
+            self.save_undo_state();
+            self.text = new_text;
+            self.content_changed = true;
         }
+        Ok(replacement_end)
     }
 
-    /// Erase the grapheme cluster at the specified (grapheme cluster) position.
-    fn erase_symbol_at(&mut self, pos: usize) -> Result<(), ()> {
-        if pos < count_grapheme_clusters(&self.text) {
-            self.text = self
-                .text
-                .graphemes(true)
-                .enumerate()
-                .filter_map(|(i, s)| if i != pos { Some(s) } else { None })
-                .collect();
-            Ok(())
-        } else {
-            Err(())
+    /// Transform the (rest of the) word at or after the cursor and move the cursor behind it.
+    fn transform_word<F: FnOnce(&str) -> String>(&mut self, f: F) -> OperationResult {
+        let cursor_byte = self.cursor_pos();
+        let (word_start, word_end) = self
+            .text
+            .split_word_bound_indices()
+            .filter(|(_, s)| s.chars().any(char::is_alphanumeric))
+            .map(|(i, s)| (i, i + s.len()))
+            .find(|&(_, end)| end > cursor_byte)
+            .ok_or(())?;
+        let start = ::std::cmp::max(word_start, cursor_byte);
+        let transformed = f(&self.text[start..word_end]);
+        let end = self.replace_range(start..word_end, &transformed)?;
+        self.cursor_pos = count_grapheme_clusters(&self.text[..end]);
+        self.additional_cursors.clear();
+        Ok(())
+    }
+
+    /// Convert the (rest of the) word at or after the cursor to uppercase and move the cursor
+    /// behind it.
+    ///
+    /// # Examples:
+    /// ```
+    /// use unsegen::widget::builtin::LineEdit;
+    ///
+    /// let mut l = LineEdit::new();
+    /// l.set("ein straße");
+    /// l.move_cursor_to_beginning_of_line();
+    /// l.uppercase_word().unwrap();
+    /// l.uppercase_word().unwrap();
+    /// assert_eq!(l.get(), "EIN STRASSE");
+    /// assert!(l.uppercase_word().is_err());
+    /// ```
+    pub fn uppercase_word(&mut self) -> OperationResult {
+        self.transform_word(str::to_uppercase)
+    }
+
+    /// Convert the (rest of the) word at or after the cursor to lowercase and move the cursor
+    /// behind it.
+    pub fn lowercase_word(&mut self) -> OperationResult {
+        self.transform_word(str::to_lowercase)
+    }
+
+    /// Capitalize the (rest of the) word at or after the cursor, i.e., convert the first character
+    /// to uppercase and the rest to lowercase, and move the cursor behind it.
+    ///
+    /// # Examples:
+    /// ```
+    /// use unsegen::widget::builtin::LineEdit;
+    ///
+    /// let mut l = LineEdit::new();
+    /// l.set("öL dEr");
+    /// l.move_cursor_to_beginning_of_line();
+    /// l.capitalize_word().unwrap();
+    /// l.capitalize_word().unwrap();
+    /// assert_eq!(l.get(), "Öl Der");
+    /// ```
+    pub fn capitalize_word(&mut self) -> OperationResult {
+        self.transform_word(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .into_iter()
+                .flat_map(char::to_uppercase)
+                .chain(chars.flat_map(char::to_lowercase))
+                .collect()
+        })
+    }
+
+    /// Erase the word before the cursor (including any whitespace between it and the cursor),
+    /// similar to "word-rubout" (Ctrl+W) in Unix terminals. Words are delimited by whitespace.
+    /// The erased text is stored in the kill buffer. See `yank`.
+    ///
+    /// All additional cursors are removed.
+    ///
+    /// # Examples:
+    /// ```
+    /// use unsegen::widget::builtin::LineEdit;
+    ///
+    /// let mut l = LineEdit::new();
+    /// l.set("héllo wörld  ");
+    /// l.delete_word_backwards().unwrap();
+    /// assert_eq!(l.get(), "héllo ");
+    /// l.delete_word_backwards().unwrap();
+    /// assert_eq!(l.get(), "");
+    /// assert!(l.delete_word_backwards().is_err());
+    /// ```
+    pub fn delete_word_backwards(&mut self) -> OperationResult {
+        if self.cursor_pos == 0 {
+            return Err(());
+        }
+        let cursor_byte = self.cursor_pos();
+        let before_cursor = self.text[..cursor_byte].trim_end();
+        let start = before_cursor
+            .char_indices()
+            .rev()
+            .find(|&(_, c)| c.is_whitespace())
+            .map(|(i, c)| i + c.len_utf8())
+            .unwrap_or(0);
+        self.kill(start..cursor_byte, KillDirection::Backwards)
+    }
+
+    /// Remove the text from the cursor to the end of the line and store it in the kill buffer,
+    /// similar to Ctrl+K in readline. See `yank`.
+    ///
+    /// All additional cursors are removed.
+    pub fn kill_to_end_of_line(&mut self) -> OperationResult {
+        let cursor_byte = self.cursor_pos();
+        let end = self.text.len();
+        self.kill(cursor_byte..end, KillDirection::Forwards)
+    }
+
+    /// Remove the text from the beginning of the line to the cursor and store it in the kill
+    /// buffer, similar to Ctrl+U in readline. See `yank`.
+    ///
+    /// All additional cursors are removed.
+    pub fn kill_to_beginning_of_line(&mut self) -> OperationResult {
+        let cursor_byte = self.cursor_pos();
+        self.kill(0..cursor_byte, KillDirection::Backwards)
+    }
+
+    /// Remove the specified byte range and store it in the kill buffer. If the previous operation
+    /// was a kill in the same direction, the removed text is added to the buffer instead.
+    fn kill(&mut self, range: Range<usize>, direction: KillDirection) -> OperationResult {
+        if range.start == range.end {
+            return Err(());
+        }
+        let killed = self.text[range.clone()].to_owned();
+        let successive = match self.last_kill {
+            Some(ref last) => {
+                last.direction == direction
+                    && last.text == self.text
+                    && last.cursor_pos == self.cursor_pos
+            }
+            None => false,
+        };
+        self.replace_range(range.clone(), "")?;
+        self.cursor_pos = count_grapheme_clusters(&self.text[..range.start]);
+        self.additional_cursors.clear();
+        match (successive, direction) {
+            (false, _) => self.kill_buffer = killed,
+            (true, KillDirection::Forwards) => self.kill_buffer.push_str(&killed),
+            (true, KillDirection::Backwards) => self.kill_buffer.insert_str(0, &killed),
+        }
+        self.last_kill = Some(LastKill {
+            direction,
+            text: self.text.clone(),
+            cursor_pos: self.cursor_pos,
+        });
+        Ok(())
+    }
+
+    /// Insert the most recently killed text before the cursor and move the cursor behind it,
+    /// similar to Ctrl+Y in readline.
+    ///
+    /// # Examples:
+    /// ```
+    /// use unsegen::widget::builtin::LineEdit;
+    ///
+    /// let mut l = LineEdit::with_text("hello wörld", 6);
+    /// l.kill_to_end_of_line().unwrap();
+    /// assert_eq!(l.get(), "hello ");
+    /// l.move_cursor_to_beginning_of_line();
+    /// l.yank().unwrap();
+    /// assert_eq!(l.get(), "wörldhello ");
+    /// assert_eq!(l.cursor_pos(), 6); // wörld|hello (byte position)
+    /// ```
+    pub fn yank(&mut self) -> OperationResult {
+        if self.kill_buffer.is_empty() {
+            return Err(());
+        }
+        let cursor_pos = self.cursor_pos;
+        let text = self.kill_buffer.clone();
+        self.insert_at(&[cursor_pos], &text)
+    }
+
+    /// Swap the grapheme cluster before the cursor with the one at the cursor and move the cursor
+    /// behind both, similar to Ctrl+T in Emacs. At the end of the line, the last two grapheme
+    /// clusters are swapped.
+    ///
+    /// All additional cursors are removed.
+    ///
+    /// # Examples:
+    /// ```
+    /// use unsegen::widget::builtin::LineEdit;
+    ///
+    /// let mut l = LineEdit::with_text("tarnspose", 2);
+    /// l.transpose_chars().unwrap();
+    /// assert_eq!(l.get(), "transpose");
+    ///
+    /// l.set("äb");
+    /// l.transpose_chars().unwrap();
+    /// assert_eq!(l.get(), "bä");
+    /// ```
+    pub fn transpose_chars(&mut self) -> OperationResult {
+        let num_graphemes = count_grapheme_clusters(&self.text);
+        let second = ::std::cmp::min(self.cursor_pos, num_graphemes.saturating_sub(1));
+        if second == 0 {
+            return Err(());
         }
+        let first = second - 1;
+        let to_byte = |i| grapheme_index_to_byte_offset(&self.text, i).unwrap_or(self.text.len());
+        let (start, middle, end) = (to_byte(first), to_byte(second), to_byte(second + 1));
+        let swapped = format!("{}{}", &self.text[middle..end], &self.text[start..middle]);
+        self.replace_range(start..end, &swapped)?;
+        self.cursor_pos = second + 1;
+        self.additional_cursors.clear();
+        Ok(())
     }
 
     /// Prepare for drawing as a `Widget`.
@@ -152,10 +1247,63 @@ impl LineEdit {
             cursor_style_active_blink_on: StyleModifier::new().invert(BoolModifyMode::Toggle),
             cursor_style_active_blink_off: StyleModifier::new(),
             cursor_style_inactive: StyleModifier::new().underline(true),
+            bracket_match_style: StyleModifier::new().bold(true),
+            bracket_mismatch_style: StyleModifier::new().fg_color(Color::Red),
+            prompt_style: StyleModifier::new(),
+            placeholder_style: StyleModifier::new().fg_color(Color::LightBlack),
+            scroll_margin: Width::new(0).unwrap(),
+            selection_style: StyleModifier::new().bg_color(Color::Blue),
         }
     }
 }
 
+const BRACKETS: &[(&str, &str)] = &[("(", ")"), ("[", "]"), ("{", "}")];
+const QUOTES: &[&str] = &["\"", "'"];
+
+/// Find the position of the delimiter matching the one at `pos`. Returns `None` if there is no
+/// delimiter at `pos` and `Some(None)` if it has no counterpart.
+fn find_matching_delimiter(graphemes: &[&str], pos: usize) -> Option<Option<usize>> {
+    let g = graphemes[pos];
+    if QUOTES.contains(&g) {
+        let num_before = graphemes[..pos].iter().filter(|&&o| o == g).count();
+        return Some(if num_before % 2 == 0 {
+            graphemes[pos + 1..]
+                .iter()
+                .position(|&o| o == g)
+                .map(|i| pos + 1 + i)
+        } else {
+            graphemes[..pos].iter().rposition(|&o| o == g)
+        });
+    }
+    for &(open, close) in BRACKETS {
+        let (forward, same, other) = if g == open {
+            (true, open, close)
+        } else if g == close {
+            (false, close, open)
+        } else {
+            continue;
+        };
+        let mut depth = 0;
+        let mut check = |i: usize| {
+            if graphemes[i] == same {
+                depth += 1;
+            } else if graphemes[i] == other {
+                if depth == 0 {
+                    return true;
+                }
+                depth -= 1;
+            }
+            false
+        };
+        return Some(if forward {
+            (pos + 1..graphemes.len()).find(|&i| check(i))
+        } else {
+            (0..pos).rev().find(|&i| check(i))
+        });
+    }
+    None
+}
+
 /// Note that there is no concept of moving up or down for a `LineEdit`.
 impl Navigatable for LineEdit {
     fn move_up(&mut self) -> OperationResult {
@@ -177,8 +1325,18 @@ impl Writable for LineEdit {
         if c == '\n' {
             Err(())
         } else {
-            self.insert(&c.to_string());
-            self.move_cursor_right()
+            if self.selection_range().is_some() {
+                return self.replace_selection(&c.to_string());
+            }
+            // Consecutively typed characters are undone as a whole.
+            self.continue_undo_group = self.typed_state.as_ref() == Some(&self.undo_state());
+            let cursors = self.cursors();
+            let res = self.insert_at(&cursors, &c.to_string());
+            self.continue_undo_group = false;
+            if res.is_ok() {
+                self.typed_state = Some(self.undo_state());
+            }
+            res
         }
     }
 }
@@ -186,19 +1344,24 @@ impl Writable for LineEdit {
 impl Editable for LineEdit {
     fn delete_forwards(&mut self) -> OperationResult {
         //i.e., "del" key
-        let to_erase = self.cursor_pos;
-        self.erase_symbol_at(to_erase)
+        if self.selection_range().is_some() {
+            return self.replace_selection("");
+        }
+        let to_erase = self.cursors();
+        self.erase_symbols_at(&to_erase)
     }
     fn delete_backwards(&mut self) -> OperationResult {
         //i.e., "backspace"
-        if self.cursor_pos > 0 {
-            let to_erase = self.cursor_pos - 1;
-            let _ = self.erase_symbol_at(to_erase);
-            let _ = self.move_cursor_left();
-            Ok(())
-        } else {
-            Err(())
+        if self.selection_range().is_some() {
+            return self.replace_selection("");
         }
+        let to_erase = self
+            .cursors()
+            .into_iter()
+            .filter(|&pos| pos > 0)
+            .map(|pos| pos - 1)
+            .collect::<Vec<_>>();
+        self.erase_symbols_at(&to_erase)
     }
     fn go_to_beginning_of_line(&mut self) -> OperationResult {
         self.move_cursor_to_beginning_of_line();
@@ -209,11 +1372,15 @@ impl Editable for LineEdit {
         Ok(())
     }
     fn clear(&mut self) -> OperationResult {
-        if self.text.is_empty() {
+        let armed = ::std::mem::replace(&mut self.clear_armed, false);
+        if self.text.is_empty() || (self.clear_requires_confirmation && !armed) {
             Err(())
         } else {
+            self.save_undo_state();
             self.text.clear();
+            self.content_changed = true;
             self.cursor_pos = 0;
+            self.additional_cursors.clear();
             Ok(())
         }
     }
@@ -227,6 +1394,12 @@ pub struct LineEditWidget<'a> {
     cursor_style_active_blink_on: StyleModifier,
     cursor_style_active_blink_off: StyleModifier,
     cursor_style_inactive: StyleModifier,
+    bracket_match_style: StyleModifier,
+    bracket_mismatch_style: StyleModifier,
+    prompt_style: StyleModifier,
+    placeholder_style: StyleModifier,
+    scroll_margin: Width,
+    selection_style: StyleModifier,
 }
 
 impl<'a> LineEditWidget<'a> {
@@ -249,33 +1422,93 @@ impl<'a> LineEditWidget<'a> {
         self.cursor_style_inactive = style;
         self
     }
+
+    /// Define the style of a bracket or quote and its counterpart if bracket matching is enabled
+    /// for the `LineEdit`. (Default: bold)
+    pub fn bracket_match_style(mut self, style: StyleModifier) -> Self {
+        self.bracket_match_style = style;
+        self
+    }
+
+    /// Define the style of a bracket or quote without a counterpart if bracket matching is enabled
+    /// for the `LineEdit`. (Default: red foreground)
+    pub fn bracket_mismatch_style(mut self, style: StyleModifier) -> Self {
+        self.bracket_mismatch_style = style;
+        self
+    }
+
+    /// Define the style of the prompt of the `LineEdit`. (Default: no change)
+    pub fn prompt_style(mut self, style: StyleModifier) -> Self {
+        self.prompt_style = style;
+        self
+    }
+
+    /// Define the style of the placeholder of the `LineEdit`. (Default: light black foreground)
+    pub fn placeholder_style(mut self, style: StyleModifier) -> Self {
+        self.placeholder_style = style;
+        self
+    }
+
+    /// Define the minimum number of columns that are kept visible left and right of the cursor
+    /// when the text is scrolled horizontally, as far as the window width permits. (Default: 0)
+    pub fn scroll_margin(mut self, margin: Width) -> Self {
+        self.scroll_margin = margin;
+        self
+    }
+
+    /// Define the style of the selected text. (Default: blue background)
+    pub fn selection_style(mut self, style: StyleModifier) -> Self {
+        self.selection_style = style;
+        self
+    }
 }
 
 impl<'a> Widget for LineEditWidget<'a> {
     fn space_demand(&self) -> Demand2D {
         Demand2D {
-            width: Demand::at_least(text_width(&self.lineedit.text) + 1),
+            width: Demand::at_least(
+                text_width(&self.lineedit.prompt)
+                    + Width::new(self.lineedit.display_width(&self.lineedit.text)).unwrap()
+                    + 1,
+            ),
             height: Demand::exact(1),
         }
     }
     fn draw(&self, mut window: Window, hints: RenderingHints) {
-        let (maybe_cursor_pos_offset, maybe_after_cursor_offset) = {
-            let mut grapheme_indices = self.lineedit.text.grapheme_indices(true);
-            let cursor_cluster = grapheme_indices.nth(self.lineedit.cursor_pos as usize);
-            let next_cluster = grapheme_indices.next();
-            (
-                cursor_cluster.map(|c: (usize, &str)| c.0),
-                next_cluster.map(|c: (usize, &str)| c.0),
-            )
-        };
-        let right_padding = 1;
-        let text_width_before_cursor = text_width(
-            &self.lineedit.text[0..maybe_after_cursor_offset.unwrap_or(self.lineedit.text.len())],
-        );
-        let draw_cursor_start_pos = ::std::cmp::min(
-            ColIndex::new(0),
-            (window.get_width() - text_width_before_cursor - right_padding).from_origin(),
+        // Scroll horizontally (if necessary) so that the cursor (and the margin around it) is
+        // visible. The offset is kept between draws so the view only moves when required.
+        let window_width = window.get_width().raw_value();
+        let cursor_byte = self.lineedit.cursor_pos();
+        let prompt_width = text_width(&self.lineedit.prompt).raw_value();
+        let cursor_text_col = self
+            .lineedit
+            .display_width(&self.lineedit.text[..cursor_byte]);
+        let cursor_col = prompt_width + cursor_text_col;
+        let cursor_width = self.lineedit.text[cursor_byte..]
+            .graphemes(true)
+            .next()
+            .map_or(1, |g| self.lineedit.grapheme_width(g, cursor_text_col));
+        let margin = ::std::cmp::max(
+            0,
+            ::std::cmp::min(
+                self.scroll_margin.raw_value(),
+                (window_width - cursor_width) / 2,
+            ),
         );
+        let mut offset = self.lineedit.scroll_offset.get() as i32;
+        // At the beginning of the text, scroll back all the way to show the prompt as well.
+        let left_bound = if cursor_byte == 0 {
+            0
+        } else {
+            cursor_col - margin
+        };
+        offset = ::std::cmp::min(offset, left_bound);
+        offset = ::std::cmp::max(offset, cursor_col + cursor_width + margin - window_width);
+        // Do not scroll further than necessary to show the end of the text (and the cursor).
+        let content_width = prompt_width + self.lineedit.display_width(&self.lineedit.text) + 1;
+        offset = ::std::cmp::min(offset, content_width - window_width);
+        let offset = ::std::cmp::max(offset, 0);
+        self.lineedit.scroll_offset.set(offset as u32);
 
         let cursor_style = match (hints.active, hints.blink) {
             (true, Blink::On) => self.cursor_style_active_blink_on,
@@ -283,31 +1516,559 @@ impl<'a> Widget for LineEditWidget<'a> {
             (false, _) => self.cursor_style_inactive,
         };
 
-        let mut cursor = Cursor::new(&mut window).position(draw_cursor_start_pos, RowIndex::new(0));
-        if let Some(cursor_pos_offset) = maybe_cursor_pos_offset {
-            let (until_cursor, from_cursor) = self.lineedit.text.split_at(cursor_pos_offset);
-            cursor.write(until_cursor);
-            if let Some(after_cursor_offset) = maybe_after_cursor_offset {
-                let (cursor_str, after_cursor) =
-                    from_cursor.split_at(after_cursor_offset - cursor_pos_offset);
-                {
-                    let mut cursor = cursor.save().style_modifier();
-                    cursor.apply_style_modifier(cursor_style);
-                    cursor.write(cursor_str);
-                }
-                cursor.write(after_cursor);
+        let cursors = self.lineedit.cursors();
+        let num_graphemes = count_grapheme_clusters(&self.lineedit.text);
+        let brackets = if self.lineedit.bracket_matching {
+            self.lineedit.bracket_pair()
+        } else {
+            None
+        };
+        let bracket_style_at = |i: usize| match brackets {
+            Some((pos, Some(matching))) if i == pos || i == matching => {
+                Some(self.bracket_match_style)
+            }
+            Some((pos, None)) if i == pos => Some(self.bracket_mismatch_style),
+            _ => None,
+        };
+        let cursor_style_at = |i: usize| {
+            if cursors.contains(&i) {
+                Some(cursor_style)
             } else {
-                let mut cursor = cursor.save().style_modifier();
-                cursor.apply_style_modifier(cursor_style);
-                cursor.write(from_cursor);
+                self.lineedit
+                    .remote_cursors
+                    .iter()
+                    .find(|(pos, _)| ::std::cmp::min(*pos, num_graphemes) == i)
+                    .map(|(_, style)| *style)
             }
+        };
+        let selection = self.lineedit.selection_range();
+        let selection_style_at = |i: usize| match selection {
+            Some(ref range) if range.contains(&i) => Some(self.selection_style),
+            _ => None,
+        };
+        let style_at = |i: usize| {
+            [
+                selection_style_at(i),
+                bracket_style_at(i),
+                cursor_style_at(i),
+            ]
+            .iter()
+            .flatten()
+            .fold(None, |below: Option<StyleModifier>, &style| {
+                Some(below.map_or(style, |below| style.on_top_of(below)))
+            })
+        };
+
+        // Collect the cells to draw (grapheme clusters with tabs expanded to spaces) and their
+        // styles, then only draw the part that is visible at the current scroll offset.
+        let mut cells: Vec<(&str, Option<StyleModifier>)> = self
+            .lineedit
+            .prompt
+            .graphemes(true)
+            .map(|g| (g, Some(self.prompt_style)))
+            .collect();
+        if self.lineedit.text.is_empty() && !self.lineedit.placeholder.is_empty() && !hints.active {
+            cells.extend(
+                self.lineedit
+                    .placeholder
+                    .graphemes(true)
+                    .map(|g| (g, Some(self.placeholder_style))),
+            );
         } else {
-            cursor.write(&self.lineedit.text);
+            let mut text_col = 0;
+            for (i, grapheme) in self.lineedit.text.graphemes(true).enumerate() {
+                let width = self.lineedit.grapheme_width(grapheme, text_col);
+                text_col += width;
+                if grapheme == "\t" {
+                    cells.extend((0..width).map(|_| (" ", style_at(i))));
+                } else {
+                    cells.push((grapheme, style_at(i)));
+                }
+            }
+            if let Some(style) = style_at(num_graphemes) {
+                cells.push((" ", Some(style)));
+            }
+        }
+        let line = cells.iter().map(|(g, _)| *g).collect::<String>();
+        let (visible, left_partial, right_partial) = visible_byte_range(
+            &line,
+            Width::new(offset).unwrap(),
+            Width::new(window_width).unwrap(),
+        );
+
+        let mut cursor = Cursor::new(&mut window);
+        let mut byte = 0;
+        for (grapheme, style) in cells {
+            let start = byte;
+            byte += grapheme.len();
+            // Grapheme clusters that straddle the window border are replaced by a space.
+            let text = if start >= visible.start && byte <= visible.end {
+                grapheme
+            } else if (left_partial && byte == visible.start)
+                || (right_partial && start == visible.end)
             {
-                let mut cursor = cursor.save().style_modifier();
-                cursor.apply_style_modifier(cursor_style);
-                cursor.write(" ");
+                " "
+            } else {
+                continue;
+            };
+            let mut cursor = cursor.save().style_modifier();
+            if let Some(style) = style {
+                cursor.apply_style_modifier(style);
             }
+            cursor.write(text);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use base::test::FakeTerminal;
+    use widget::WidgetExt;
+
+    /// Draw the (active) `LineEdit` with a bold cursor into a single row of the given width after
+    /// applying `configure` to the widget.
+    fn draw<'a, W: Widget>(
+        edit: &'a LineEdit,
+        width: u32,
+        configure: impl FnOnce(LineEditWidget<'a>) -> W,
+    ) -> FakeTerminal {
+        let mut term = FakeTerminal::with_size((width, 1));
+        configure(
+            edit.as_widget()
+                .cursor_blink_on(StyleModifier::new().bold(true)),
+        )
+        .draw(
+            term.create_root_window(),
+            RenderingHints::default().active(true).blink(Blink::On),
+        );
+        term
+    }
+
+    #[test]
+    fn test_multiple_cursors_write() {
+        let mut l = LineEdit::new();
+        l.set("abc");
+        l.add_cursor(1).unwrap();
+        l.add_cursor(0).unwrap();
+        l.write('x').unwrap();
+        assert_eq!(l.get(), "xaxbcx");
+        assert_eq!(l.cursors(), vec![1, 3, 6]);
+        assert!(l.add_cursor(3).is_err());
+    }
+
+    #[test]
+    fn test_multiple_cursors_delete() {
+        let mut l = LineEdit::new();
+        l.set("abcd");
+        l.add_cursor(2).unwrap();
+        l.delete_backwards().unwrap();
+        assert_eq!(l.get(), "ac");
+        assert_eq!(l.cursors(), vec![1, 2]);
+
+        l.move_cursor_to_beginning_of_line();
+        l.add_cursor(1).unwrap();
+        l.delete_forwards().unwrap();
+        assert_eq!(l.get(), "");
+        assert_eq!(l.cursors(), vec![0]);
+    }
+
+    #[test]
+    fn test_multiple_cursors_merge() {
+        let mut l = LineEdit::new();
+        l.set("ab");
+        l.add_cursor(1).unwrap();
+        l.move_cursor_right().unwrap_err();
+        assert_eq!(l.cursors(), vec![2]);
+    }
+
+    #[test]
+    fn test_multiple_cursors_draw() {
+        let mut l = LineEdit::new();
+        l.set("abc");
+        l.add_cursor(1).unwrap();
+        draw(&l, 5, |w| w).assert_looks_like("a*b*c* * ");
+    }
+
+    #[test]
+    fn test_remote_cursors_draw() {
+        let mut l = LineEdit::new();
+        l.set("abc");
+        l.set_cursor_pos(0).unwrap();
+        l.set_remote_cursors(vec![
+            (0, StyleModifier::new().italic(true)),
+            (1, StyleModifier::new().bold(true)),
+            (10, StyleModifier::new().bold(true)),
+        ]);
+        draw(&l, 5, |w| w.cursor_blink_on(StyleModifier::new())).assert_looks_like("a*b*c* * ");
+        assert_eq!(l.get(), "abc");
+        assert_eq!(l.cursors(), vec![0]);
+    }
+
+    #[test]
+    fn test_matching_delimiter() {
+        let find = |text: &str, pos: usize| {
+            let graphemes = text.graphemes(true).collect::<Vec<_>>();
+            find_matching_delimiter(&graphemes, pos)
+        };
+        assert_eq!(find("a(b)", 0), None);
+        assert_eq!(find("(a(b)c)", 0), Some(Some(6)));
+        assert_eq!(find("(a(b)c)", 4), Some(Some(2)));
+        assert_eq!(find("(a(b)c)", 6), Some(Some(0)));
+        assert_eq!(find("(a[b)", 0), Some(Some(4)));
+        assert_eq!(find("(ab", 0), Some(None));
+        assert_eq!(find("'a' 'b'", 4), Some(Some(6)));
+        assert_eq!(find("'a' 'b'", 2), Some(Some(0)));
+        assert_eq!(find("'ab", 0), Some(None));
+    }
+
+    #[test]
+    fn test_bracket_matching_draw() {
+        fn style(w: LineEditWidget) -> LineEditWidget {
+            w.cursor_blink_on(StyleModifier::new())
+                .bracket_mismatch_style(StyleModifier::new().bold(true))
+        }
+        let mut l = LineEdit::new();
+        l.set("f(a)");
+        draw(&l, 6, style).assert_looks_like("f(a)  ");
+
+        l.set_bracket_matching(true);
+        draw(&l, 6, style).assert_looks_like("f*(*a*)*  ");
+
+        l.set_cursor_pos(2).unwrap();
+        draw(&l, 6, style).assert_looks_like("f*(*a*)*  ");
+
+        l.set_cursor_pos(0).unwrap();
+        draw(&l, 6, style).assert_looks_like("f(a)  ");
+
+        l.set("f(a");
+        l.set_cursor_pos(1).unwrap();
+        draw(&l, 6, style).assert_looks_like("f*(*a   ");
+    }
+
+    #[test]
+    fn test_prompt_draw() {
+        let mut l = LineEdit::new();
+        l.set_prompt("> ");
+        l.set("abcd");
+        fn style(w: LineEditWidget) -> LineEditWidget {
+            w.prompt_style(StyleModifier::new().bold(true))
+                .cursor_blink_on(StyleModifier::new())
+        }
+        draw(&l, 5, style).assert_looks_like("abcd ");
+
+        l.set_cursor_pos(0).unwrap();
+        draw(&l, 5, style).assert_looks_like("*>** *abc");
+        assert!(l.move_cursor_left().is_err());
+        assert_eq!(l.get(), "abcd");
+    }
+
+    #[test]
+    fn test_delete_word_backwards() {
+        let mut l = LineEdit::with_text("héllo wörld fün", 10);
+        l.delete_word_backwards().unwrap();
+        assert_eq!(l.get(), "héllo d fün");
+        assert_eq!(l.cursors(), vec![6]);
+
+        l.delete_word_backwards().unwrap();
+        assert_eq!(l.get(), "d fün");
+        assert_eq!(l.cursors(), vec![0]);
+        assert!(l.delete_word_backwards().is_err());
+
+        let mut l = LineEdit::with_text("a \t b", 5);
+        l.delete_word_backwards().unwrap();
+        assert_eq!(l.get(), "a \t ");
+        l.delete_word_backwards().unwrap();
+        assert_eq!(l.get(), "");
+    }
+
+    #[test]
+    fn test_kill_and_yank() {
+        let mut l = LineEdit::with_text("ab cd ef", 4);
+        assert!(l.yank().is_err());
+        l.kill_to_beginning_of_line().unwrap();
+        assert_eq!(l.get(), "d ef");
+        l.move_cursor_to_end_of_line();
+        l.yank().unwrap();
+        assert_eq!(l.get(), "d efab c");
+        assert_eq!(l.cursors(), vec![8]);
+
+        // Successive kills in the same direction are accumulated.
+        let mut l = LineEdit::with_text("ab cd ef", 6);
+        l.delete_word_backwards().unwrap();
+        l.delete_word_backwards().unwrap();
+        assert_eq!(l.kill_buffer, "ab cd ");
+        l.kill_to_end_of_line().unwrap();
+        assert_eq!(l.kill_buffer, "ef");
+        assert_eq!(l.get(), "");
+
+        let mut l = LineEdit::with_text("ab cd ef", 6);
+        l.delete_word_backwards().unwrap();
+        l.move_cursor_right().unwrap();
+        l.kill_to_beginning_of_line().unwrap();
+        assert_eq!(l.kill_buffer, "ab e");
+        assert_eq!(l.get(), "f");
+    }
+
+    #[test]
+    fn test_max_length() {
+        let mut l = LineEdit::new();
+        l.set_max_length(Some(3));
+        l.insert("ab").unwrap();
+        assert!(l.insert("cd").is_err());
+        assert_eq!(l.get(), "ab");
+        l.move_cursor_to_end_of_line();
+        l.write('😀').unwrap();
+        assert!(l.write('x').is_err());
+        assert_eq!(l.get(), "ab😀");
+
+        l.set("abcd");
+        l.delete_backwards().unwrap();
+        assert_eq!(l.get(), "abc");
+        assert!(l.push_char('d').is_err());
+    }
+
+    #[test]
+    fn test_placeholder_draw() {
+        let mut l = LineEdit::new();
+        l.set_placeholder("find");
+        fn style(w: LineEditWidget) -> LineEditWidget {
+            w.placeholder_style(StyleModifier::new().bold(true))
+                .cursor_blink_on(StyleModifier::new())
+                .cursor_inactive(StyleModifier::new())
+        }
+        fn inactive(w: LineEditWidget<'_>) -> impl Widget + '_ {
+            style(w).with_hints(|h| h.active(false))
+        }
+        draw(&l, 5, inactive).assert_looks_like("*f**i**n**d* ");
+        draw(&l, 5, style).assert_looks_like("     ");
+
+        l.write('a').unwrap();
+        draw(&l, 5, inactive).assert_looks_like("a    ");
+        assert_eq!(l.get(), "a");
+    }
+
+    #[test]
+    fn test_erase_multibyte() {
+        let mut l = LineEdit::new();
+        l.set("héllo wörld");
+        l.move_cursor_left().unwrap();
+        l.delete_forwards().unwrap();
+        assert_eq!(l.get(), "héllo wörl");
+        assert!(l.delete_forwards().is_err());
+        l.delete_backwards().unwrap();
+        assert_eq!(l.get(), "héllo wör");
+
+        // "e" followed by a combining acute accent forms a single grapheme cluster.
+        let mut l = LineEdit::new();
+        l.set("cafe\u{301}");
+        l.delete_backwards().unwrap();
+        assert_eq!(l.get(), "caf");
+        l.set("ab\u{301}c");
+        l.set_cursor_pos(1).unwrap();
+        l.delete_forwards().unwrap();
+        assert_eq!(l.get(), "ac");
+    }
+
+    #[test]
+    fn test_horizontal_scroll() {
+        fn margin(w: LineEditWidget) -> LineEditWidget {
+            w.scroll_margin(Width::new(1).unwrap())
         }
+        let mut l = LineEdit::new();
+        l.set("abcdefgh");
+        draw(&l, 5, |w| w).assert_looks_like("efgh* *");
+        l.set_cursor_pos(5).unwrap();
+        draw(&l, 5, |w| w).assert_looks_like("e*f*gh ");
+        l.set_cursor_pos(3).unwrap();
+        draw(&l, 5, |w| w).assert_looks_like("*d*efgh");
+        draw(&l, 5, margin).assert_looks_like("c*d*efg");
+        l.move_cursor_to_end_of_line();
+        draw(&l, 5, margin).assert_looks_like("efgh* *");
+
+        // Wide grapheme clusters that are cut off by the window border are not drawn.
+        let mut l = LineEdit::new();
+        l.set("ab山cd");
+        draw(&l, 4, |w| w).assert_looks_like(" cd* *");
+        l.set_cursor_pos(0).unwrap();
+        draw(&l, 3, |w| w).assert_looks_like("*a*b ");
+    }
+
+    #[test]
+    fn test_wide_grapheme_cursor() {
+        // Both cells of a wide grapheme cluster under the cursor are highlighted.
+        let mut l = LineEdit::new();
+        l.set("漢字");
+        l.set_cursor_pos(0).unwrap();
+        draw(&l, 6, |w| w).assert_looks_like("*漢***字  ");
+        l.move_cursor_right().unwrap();
+        draw(&l, 6, |w| w).assert_looks_like("漢*字***  ");
+        l.move_cursor_right().unwrap();
+        draw(&l, 6, |w| w).assert_looks_like("漢字* * ");
+
+        // The whole wide grapheme cluster under the cursor is scrolled into view.
+        l.set_cursor_pos(3).unwrap();
+        draw(&l, 3, |w| w).assert_looks_like(" *字***");
+    }
+
+    #[test]
+    fn test_tab_width() {
+        let mut l = LineEdit::new();
+        l.set_tab_width(Width::new(4).unwrap());
+        l.set("a\tb\tc");
+        assert_eq!(l.as_widget().space_demand().width, Demand::at_least(10));
+        draw(&l, 10, |w| w).assert_looks_like("a   b   c* *");
+
+        // The cursor covers the whole expanded tab.
+        l.set_cursor_pos(1).unwrap();
+        draw(&l, 10, |w| w).assert_looks_like("a* ** ** *b   c ");
+
+        l.set_tab_width(Width::new(2).unwrap());
+        draw(&l, 10, |w| w).assert_looks_like("a* *b c     ");
+        assert_eq!(l.get(), "a\tb\tc");
+
+        l.set_max_display_width(Some(Width::new(6).unwrap()));
+        l.move_cursor_to_end_of_line();
+        assert!(l.write('\t').is_ok());
+        assert!(l.write('d').is_err());
+    }
+
+    #[test]
+    fn test_undo_redo() {
+        let mut l = LineEdit::new();
+        l.write('a').unwrap();
+        l.write('b').unwrap();
+        l.move_cursor_left().unwrap();
+        l.write('c').unwrap();
+        assert_eq!(l.get(), "acb");
+        l.clear().unwrap();
+
+        l.undo().unwrap();
+        assert_eq!(l.get(), "acb");
+        assert_eq!(l.cursors(), vec![2]);
+        l.undo().unwrap();
+        assert_eq!(l.get(), "ab");
+        assert_eq!(l.cursors(), vec![1]);
+        l.undo().unwrap();
+        assert_eq!(l.get(), "");
+        assert!(l.undo().is_err());
+
+        l.redo().unwrap();
+        assert_eq!(l.get(), "ab");
+        assert!(l.can_redo());
+        l.write('x').unwrap();
+        assert!(!l.can_redo());
+        assert!(l.redo().is_err());
+    }
+
+    #[test]
+    fn test_undo_depth() {
+        let mut l = LineEdit::new();
+        l.set_undo_depth(2);
+        l.set("a");
+        l.set("b");
+        l.set("c");
+        l.undo().unwrap();
+        l.undo().unwrap();
+        assert!(!l.can_undo());
+        assert_eq!(l.get(), "a");
+    }
+
+    #[test]
+    fn test_validator() {
+        let mut l = LineEdit::new();
+        l.set("12a");
+        l.set_validator(Box::new(|text| text.parse::<u32>().is_ok()));
+        assert!(l.write('3').is_err());
+        assert!(l.push_str("4").is_err());
+        l.delete_backwards().unwrap();
+        assert_eq!(l.get(), "12");
+        l.write('3').unwrap();
+        l.kill_to_beginning_of_line().unwrap();
+        l.yank().unwrap();
+        assert_eq!(l.get(), "123");
+        l.clear().unwrap();
+        assert_eq!(l.get(), "");
+    }
+
+    #[test]
+    fn test_selection() {
+        let mut l = LineEdit::with_text("héllo wörld", 5);
+        assert_eq!(l.selected_text(), None);
+        l.select_right().unwrap();
+        l.select_right().unwrap();
+        l.select_right().unwrap();
+        l.select_left().unwrap();
+        assert_eq!(l.selected_text(), Some(" w"));
+        l.move_cursor_left().unwrap();
+        assert_eq!(l.selected_text(), None);
+
+        l.select_left().unwrap();
+        l.select_left().unwrap();
+        assert_eq!(l.selected_text(), Some("o "));
+        l.delete_backwards().unwrap();
+        assert_eq!(l.get(), "héllwörld");
+        assert_eq!(l.cursors(), vec![4]);
+
+        l.select_right().unwrap();
+        l.write('_').unwrap();
+        assert_eq!(l.get(), "héll_örld");
+        assert_eq!(l.cursors(), vec![5]);
+    }
+
+    #[test]
+    fn test_selection_draw() {
+        let mut l = LineEdit::with_text("abcd", 1);
+        l.select_right().unwrap();
+        l.select_right().unwrap();
+        draw(&l, 5, |w| {
+            w.cursor_blink_on(StyleModifier::new())
+                .selection_style(StyleModifier::new().bold(true))
+        })
+        .assert_looks_like("a*b**c*d ");
+    }
+
+    #[test]
+    fn test_history() {
+        let mut l = LineEdit::new();
+        l.set_history_limit(2);
+        l.push_history("a".to_owned());
+        l.push_history("b".to_owned());
+        l.push_history("b".to_owned());
+        l.push_history("c".to_owned());
+        assert_eq!(l.history, vec!["b".to_owned(), "c".to_owned()]);
+        assert!(l.history_next().is_err());
+
+        l.history_prev().unwrap();
+        l.history_prev().unwrap();
+        assert_eq!(l.get(), "b");
+        assert_eq!(l.cursors(), vec![1]);
+        l.history_next().unwrap();
+        assert_eq!(l.get(), "c");
+        l.history_next().unwrap();
+        assert_eq!(l.get(), "");
+    }
+
+    #[test]
+    fn test_transpose_chars() {
+        let mut l = LineEdit::with_text("a", 1);
+        assert!(l.transpose_chars().is_err());
+
+        let mut l = LineEdit::with_text("abc", 0);
+        assert!(l.transpose_chars().is_err());
+        l.set_cursor_pos(1).unwrap();
+        l.transpose_chars().unwrap();
+        assert_eq!(l.get(), "bac");
+        assert_eq!(l.cursors(), vec![2]);
+        l.transpose_chars().unwrap();
+        assert_eq!(l.get(), "bca");
+        assert_eq!(l.cursors(), vec![3]);
+        l.transpose_chars().unwrap();
+        assert_eq!(l.get(), "bac");
+        assert_eq!(l.cursors(), vec![3]);
+
+        let mut l = LineEdit::with_text("e\u{301}漢", 2);
+        l.transpose_chars().unwrap();
+        assert_eq!(l.get(), "漢e\u{301}");
     }
 }
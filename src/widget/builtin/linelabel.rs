@@ -0,0 +1,323 @@
+//! A single line of (possibly differently styled) text.
+use base::basic_types::*;
+use base::{Cursor, GraphemeCluster, StyleModifier, Window};
+use unicode_segmentation::UnicodeSegmentation;
+use widget::{text_width, Alignment, Demand, Demand2D, RenderingHints, Widget};
+
+const ELLIPSIS: &str = "…";
+
+/// Behavior of a `LineLabel` within a window that is narrower than its text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Truncation {
+    /// Draw the text as far as it fits into the window.
+    Clip,
+    /// Drop grapheme clusters from the end of the text and draw "…" in their place.
+    EllipsisEnd,
+    /// Drop grapheme clusters from the beginning of the text and draw "…" in their place.
+    EllipsisStart,
+}
+
+/// A single line of text that is composed of a sequence of segments, each of which is drawn with
+/// its own style.
+///
+/// This is useful for status bars or similar widgets that are assembled from distinct pieces.
+///
+/// # Examples:
+/// ```
+/// use unsegen::base::StyleModifier;
+/// use unsegen::widget::builtin::LineLabel;
+///
+/// let label = LineLabel::from_segments(vec![
+///     ("NORMAL".to_owned(), StyleModifier::new().bold(true)),
+///     (" main.rs".to_owned(), StyleModifier::new()),
+/// ]);
+/// assert_eq!(label.text(), "NORMAL main.rs");
+/// ```
+///
+/// Optionally, a secondary text (e.g., a key binding in a menu entry) can be drawn flush right
+/// within the same row. It is dropped if the window is too narrow to show both texts.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LineLabel {
+    segments: Vec<(String, StyleModifier)>,
+    secondary: (String, StyleModifier),
+    filler: GraphemeCluster,
+    alignment: Alignment,
+    truncation: Truncation,
+}
+
+impl LineLabel {
+    /// Create a label consisting of a single unstyled segment.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self::from_segments(vec![(text.into(), StyleModifier::new())])
+    }
+
+    /// Create a label from a sequence of segments that will be drawn back-to-back, each with its
+    /// associated style.
+    pub fn from_segments(segments: Vec<(String, StyleModifier)>) -> Self {
+        LineLabel {
+            segments,
+            secondary: (String::new(), StyleModifier::new()),
+            filler: GraphemeCluster::space(),
+            alignment: Alignment::Left,
+            truncation: Truncation::Clip,
+        }
+    }
+
+    /// Create a label with a primary text on the left and a secondary text on the right, e.g.,
+    /// `Open file          Ctrl+O`.
+    ///
+    /// # Examples:
+    /// ```
+    /// use unsegen::base::{GraphemeCluster, StyleModifier};
+    /// use unsegen::widget::builtin::LineLabel;
+    ///
+    /// let label = LineLabel::with_secondary("Open file", "Ctrl+O")
+    ///     .secondary_style(StyleModifier::new().italic(true))
+    ///     .filler(GraphemeCluster::try_from('.').unwrap());
+    /// ```
+    pub fn with_secondary(primary: impl Into<String>, secondary: impl Into<String>) -> Self {
+        let mut label = Self::new(primary);
+        label.secondary.0 = secondary.into();
+        label
+    }
+
+    /// Specify the style of the secondary text. (Default: no change)
+    pub fn secondary_style(mut self, style: StyleModifier) -> Self {
+        self.secondary.1 = style;
+        self
+    }
+
+    /// Specify the grapheme cluster that fills the space between the primary and secondary text.
+    /// (Default: space)
+    pub fn filler(mut self, filler: GraphemeCluster) -> Self {
+        self.filler = filler;
+        self
+    }
+
+    /// Specify where the text is drawn if the window is wider than the text. The alignment is
+    /// ignored if a secondary text is drawn. (Default: `Alignment::Left`)
+    ///
+    /// # Examples:
+    /// ```
+    /// use unsegen::widget::builtin::LineLabel;
+    /// use unsegen::widget::Alignment;
+    ///
+    /// let mut clock = LineLabel::new("12:34");
+    /// clock.set_alignment(Alignment::Right);
+    /// assert_eq!(clock.alignment(), Alignment::Right);
+    /// ```
+    pub fn set_alignment(&mut self, alignment: Alignment) {
+        self.alignment = alignment;
+    }
+
+    /// Get the alignment of the text. See `set_alignment`.
+    pub fn alignment(&self) -> Alignment {
+        self.alignment
+    }
+
+    /// Specify how the text is shortened if the window is too narrow to show all of it. Grapheme
+    /// clusters are always dropped as a whole and the ellipsis is drawn in the style of the first
+    /// grapheme cluster it replaces. (Default: `Truncation::Clip`)
+    ///
+    /// # Examples:
+    /// ```
+    /// use unsegen::widget::builtin::{LineLabel, Truncation};
+    ///
+    /// let mut path = LineLabel::new("/home/user/projects/unsegen/src/lib.rs");
+    /// path.set_truncation(Truncation::EllipsisStart);
+    /// assert_eq!(path.truncation(), Truncation::EllipsisStart);
+    /// ```
+    pub fn set_truncation(&mut self, truncation: Truncation) {
+        self.truncation = truncation;
+    }
+
+    /// Get the truncation behavior of the label. See `set_truncation`.
+    pub fn truncation(&self) -> Truncation {
+        self.truncation
+    }
+
+    /// Append a segment to the end of the primary text.
+    pub fn push_segment(&mut self, text: impl Into<String>, style: StyleModifier) {
+        self.segments.push((text.into(), style));
+    }
+
+    /// Get the segments that make up the label.
+    pub fn segments(&self) -> &[(String, StyleModifier)] {
+        &self.segments
+    }
+
+    /// Get the full (unstyled) text of the label.
+    pub fn text(&self) -> String {
+        self.segments.iter().map(|(s, _)| s.as_str()).collect()
+    }
+
+    /// Get the secondary text of the label (which is empty if none was specified).
+    pub fn secondary(&self) -> &str {
+        &self.secondary.0
+    }
+
+    fn primary_width(&self) -> Width {
+        self.segments.iter().map(|(s, _)| text_width(s)).sum()
+    }
+
+    /// Get the (styled) grapheme clusters of the primary text that are drawn in a window of the
+    /// given width, including an ellipsis if the text has to be truncated.
+    fn visible_graphemes(&self, available: Width) -> Vec<(&str, StyleModifier)> {
+        let mut graphemes = self
+            .segments
+            .iter()
+            .flat_map(|(s, style)| s.graphemes(true).map(move |g| (g, *style)))
+            .collect::<Vec<_>>();
+        if self.truncation == Truncation::Clip || self.primary_width() <= available {
+            return graphemes;
+        }
+        let mut remaining = available.raw_value() - text_width(ELLIPSIS).raw_value();
+        if remaining < 0 {
+            return Vec::new();
+        }
+        // Truncating at the start is the same as truncating the reversed text at the end.
+        if self.truncation == Truncation::EllipsisStart {
+            graphemes.reverse();
+        }
+        let mut visible = Vec::new();
+        for (grapheme, style) in graphemes {
+            let width = text_width(grapheme).raw_value();
+            if width > remaining {
+                visible.push((ELLIPSIS, style));
+                break;
+            }
+            remaining -= width;
+            visible.push((grapheme, style));
+        }
+        if self.truncation == Truncation::EllipsisStart {
+            visible.reverse();
+        }
+        visible
+    }
+}
+
+impl Widget for LineLabel {
+    fn space_demand(&self) -> Demand2D {
+        // Extra space is used for alignment (or the filler) and the full text is advertised as
+        // the minimum, even though it can be truncated if necessary.
+        let width = if self.secondary.0.is_empty() {
+            Demand::at_least(self.primary_width())
+        } else {
+            Demand::at_least(self.primary_width() + 1 + text_width(&self.secondary.0))
+        };
+        Demand2D {
+            width,
+            height: Demand::exact(1),
+        }
+    }
+    fn draw(&self, mut window: Window, _hints: RenderingHints) {
+        let secondary_start = (window.get_width() - text_width(&self.secondary.0)).from_origin();
+        let visible = self.visible_graphemes(window.get_width());
+        let free_space = window.get_width() - self.primary_width();
+        let start_col = if self.secondary.0.is_empty() {
+            match self.alignment {
+                Alignment::Left => ColIndex::new(0),
+                Alignment::Center => (free_space / 2).from_origin().positive_or_zero(),
+                Alignment::Right => free_space.from_origin().positive_or_zero(),
+            }
+        } else {
+            ColIndex::new(0)
+        };
+        let mut cursor = Cursor::new(&mut window).position(start_col, RowIndex::new(0));
+        for (grapheme, style) in visible {
+            let mut cursor = cursor.save().style_modifier();
+            cursor.apply_style_modifier(style);
+            cursor.write(grapheme);
+        }
+        if self.secondary.0.is_empty() || secondary_start < (self.primary_width() + 1).from_origin()
+        {
+            return;
+        }
+        let filler_width = self.filler.width() as i32;
+        if filler_width > 0 {
+            while cursor.get_position().0 + filler_width <= secondary_start {
+                cursor.write(self.filler.as_str());
+            }
+        }
+        cursor.move_to_x(secondary_start);
+        cursor.apply_style_modifier(self.secondary.1);
+        cursor.write(&self.secondary.0);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use base::test::FakeTerminal;
+
+    #[test]
+    fn test_segments() {
+        let label = LineLabel::from_segments(vec![
+            ("ab".to_owned(), StyleModifier::new().bold(true)),
+            ("cd".to_owned(), StyleModifier::new()),
+        ]);
+        assert_eq!(label.space_demand().width, Demand::at_least(4));
+
+        let mut term = FakeTerminal::with_size((5, 1));
+        label.draw(term.create_root_window(), RenderingHints::default());
+        term.assert_looks_like("*a**b*cd ");
+    }
+
+    #[test]
+    fn test_secondary() {
+        let label =
+            LineLabel::with_secondary("ab", "cd").filler(GraphemeCluster::try_from('.').unwrap());
+        assert_eq!(label.space_demand().width, Demand::at_least(5));
+
+        let mut term = FakeTerminal::with_size((7, 1));
+        label.draw(term.create_root_window(), RenderingHints::default());
+        term.assert_looks_like("ab...cd");
+
+        let mut term = FakeTerminal::with_size((4, 1));
+        label.draw(term.create_root_window(), RenderingHints::default());
+        term.assert_looks_like("ab  ");
+    }
+
+    #[test]
+    fn test_alignment() {
+        let mut label = LineLabel::new("ab");
+        let draw = |label: &LineLabel, width: u32| {
+            let mut term = FakeTerminal::with_size((width, 1));
+            label.draw(term.create_root_window(), RenderingHints::default());
+            term
+        };
+        draw(&label, 5).assert_looks_like("ab   ");
+        label.set_alignment(Alignment::Center);
+        draw(&label, 5).assert_looks_like(" ab  ");
+        draw(&label, 6).assert_looks_like("  ab  ");
+        label.set_alignment(Alignment::Right);
+        draw(&label, 5).assert_looks_like("   ab");
+        draw(&label, 1).assert_looks_like("a");
+    }
+
+    #[test]
+    fn test_truncation() {
+        let mut label = LineLabel::from_segments(vec![
+            ("ab".to_owned(), StyleModifier::new().bold(true)),
+            ("c漢d".to_owned(), StyleModifier::new()),
+        ]);
+        let draw = |label: &LineLabel, width: u32| {
+            let mut term = FakeTerminal::with_size((width, 1));
+            label.draw(term.create_root_window(), RenderingHints::default());
+            term
+        };
+        draw(&label, 6).assert_looks_like("*a**b*c漢d");
+        draw(&label, 3).assert_looks_like("*a**b*c");
+
+        label.set_truncation(Truncation::EllipsisEnd);
+        draw(&label, 6).assert_looks_like("*a**b*c漢d");
+        draw(&label, 5).assert_looks_like("*a**b*c… ");
+        draw(&label, 3).assert_looks_like("*a**b*…");
+        draw(&label, 1).assert_looks_like("*…*");
+
+        label.set_truncation(Truncation::EllipsisStart);
+        draw(&label, 5).assert_looks_like("*…*c漢d");
+        draw(&label, 3).assert_looks_like("…d ");
+        assert_eq!(label.space_demand().width, Demand::at_least(6));
+    }
+}
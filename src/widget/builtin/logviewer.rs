@@ -1,12 +1,16 @@
 //! A scrollable, append-only buffer of lines.
 use base::basic_types::*;
-use base::{Cursor, Window, WrappingMode};
+use base::{Cursor, GraphemeCluster, StyleModifier, Window, WrappingMode};
 use input::{OperationResult, Scrollable};
 use std::fmt;
 use std::ops::Range;
 use widget::{Demand, Demand2D, RenderingHints, Widget};
 
 /// A scrollable, append-only buffer of lines.
+///
+/// A line is complete once a newline has been written. Text written after the last newline forms
+/// an incomplete line that is shown as well, but an empty incomplete line (e.g., after writing
+/// `"a\nb\n"`) is not, i.e., the viewer contains the two lines "a" and "b" in this case.
 pub struct LogViewer {
     storage: Vec<String>, // Invariant: always holds at least one line, does not contain newlines
     scrollback_position: Option<LineIndex>,
@@ -14,7 +18,8 @@ pub struct LogViewer {
 }
 
 impl LogViewer {
-    /// Create an empty `LogViewer`. Add lines by writing to the viewer as `std::io::Write`.
+    /// Create an empty `LogViewer`. Add lines by writing to the viewer as `std::fmt::Write`.
+    /// Both `\n` and `\r\n` line endings are accepted.
     pub fn new() -> Self {
         let mut storage = Vec::new();
         storage.push(String::new()); //Fullfil invariant (at least one line)
@@ -26,7 +31,12 @@ impl LogViewer {
     }
 
     fn num_lines_stored(&self) -> usize {
-        self.storage.len() // Per invariant: no newlines in storage
+        // Per invariant: no newlines in storage. An empty active line after a complete line is not
+        // counted, so that text ending in a newline does not produce a trailing empty line.
+        match self.storage.len() {
+            n if n > 1 && self.storage[n - 1].is_empty() => n - 1,
+            n => n,
+        }
     }
 
     fn current_line_index(&self) -> LineIndex {
@@ -47,8 +57,14 @@ impl LogViewer {
     }
 
     /// Prepare for drawing as a `Widget`.
-    pub fn as_widget<'a>(&'a self) -> impl Widget + 'a {
-        LogViewerWidget { inner: self }
+    pub fn as_widget<'a>(&'a self) -> LogViewerWidget<'a> {
+        LogViewerWidget {
+            inner: self,
+            indicator_above: None,
+            indicator_below: None,
+            indicator_style: StyleModifier::new(),
+            indicator_position: IndicatorPosition::Right,
+        }
     }
 }
 
@@ -59,7 +75,12 @@ impl fmt::Write for LogViewer {
         while let Some(newline_offset) = s.find('\n') {
             let mut line: String = s.drain(..(newline_offset + 1)).collect();
             line.pop(); //Remove the \n
-            self.active_line_mut().push_str(&line);
+            let active_line = self.active_line_mut();
+            active_line.push_str(&line);
+            // Also strip the \r of a \r\n line ending (possibly written in a previous call)
+            if active_line.ends_with('\r') {
+                active_line.pop();
+            }
             self.storage.push(String::new());
         }
         self.active_line_mut().push_str(&s);
@@ -114,8 +135,65 @@ impl Scrollable for LogViewer {
     }
 }
 
-struct LogViewerWidget<'a> {
+/// The column in which a `LogViewerWidget` draws its scroll indicators.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IndicatorPosition {
+    /// The leftmost column of the window.
+    Left,
+    /// The rightmost column of the window.
+    Right,
+}
+
+/// A `Widget` representing a `LogViewer`
+///
+/// It allows for customization of indicators that show whether there is more content above or
+/// below the visible range.
+pub struct LogViewerWidget<'a> {
     inner: &'a LogViewer,
+    indicator_above: Option<GraphemeCluster>,
+    indicator_below: Option<GraphemeCluster>,
+    indicator_style: StyleModifier,
+    indicator_position: IndicatorPosition,
+}
+
+impl<'a> LogViewerWidget<'a> {
+    /// Draw the given grapheme cluster in the top row if there is content above the visible
+    /// range, e.g., '▲'. (Default: None)
+    pub fn indicator_above(mut self, indicator: GraphemeCluster) -> Self {
+        self.indicator_above = Some(indicator);
+        self
+    }
+
+    /// Draw the given grapheme cluster in the bottom row if there is content below the visible
+    /// range, e.g., '▼'. (Default: None)
+    pub fn indicator_below(mut self, indicator: GraphemeCluster) -> Self {
+        self.indicator_below = Some(indicator);
+        self
+    }
+
+    /// Define the style that scroll indicators will be drawn with. (Default: no change)
+    pub fn indicator_style(mut self, style: StyleModifier) -> Self {
+        self.indicator_style = style;
+        self
+    }
+
+    /// Define the column that scroll indicators will be drawn in. (Default: Right)
+    pub fn indicator_position(mut self, position: IndicatorPosition) -> Self {
+        self.indicator_position = position;
+        self
+    }
+
+    fn draw_indicator(&self, window: &mut Window, indicator: &GraphemeCluster, row: RowIndex) {
+        let col = match self.indicator_position {
+            IndicatorPosition::Left => ColIndex::new(0),
+            IndicatorPosition::Right => (window.get_width() - indicator.width() as i32)
+                .from_origin()
+                .positive_or_zero(),
+        };
+        let mut cursor = Cursor::new(window).position(col, row);
+        cursor.apply_style_modifier(self.indicator_style);
+        cursor.write(indicator.as_str());
+    }
 }
 
 impl<'a> Widget for LogViewerWidget<'a> {
@@ -148,5 +226,76 @@ impl<'a> Widget for LogViewerWidget<'a> {
             cursor.writeln(&line);
             cursor.move_by(ColDiff::new(0), RowDiff::new(-num_auto_wraps) - 2);
         }
+        // The cursor is now placed in the row above the first line that was drawn.
+        let content_above = cursor.get_position().1 + 1 < 0;
+        let content_below = end_line.raw_value() + 1 < self.inner.num_lines_stored();
+
+        if let (true, Some(indicator)) = (content_above, &self.indicator_above) {
+            self.draw_indicator(&mut window, indicator, RowIndex::new(0));
+        }
+        if let (true, Some(indicator)) = (content_below, &self.indicator_below) {
+            self.draw_indicator(&mut window, indicator, y_start.from_origin());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use base::test::FakeTerminal;
+    use std::fmt::Write;
+
+    fn test_logviewer<F: Fn(&mut LogViewer)>(after: &str, action: F) {
+        let mut term = FakeTerminal::with_size((3, 2));
+        let mut logviewer = LogViewer::new();
+        write!(logviewer, "a\nb\nc\nd").unwrap();
+        action(&mut logviewer);
+        logviewer
+            .as_widget()
+            .indicator_above(GraphemeCluster::try_from('^').unwrap())
+            .indicator_below(GraphemeCluster::try_from('v').unwrap())
+            .draw(term.create_root_window(), RenderingHints::default());
+        term.assert_looks_like(after);
+    }
+
+    #[test]
+    fn test_scroll_indicators() {
+        test_logviewer("c ^|d  ", |_| {});
+        test_logviewer("b ^|c v", |l| {
+            l.scroll_backwards().unwrap();
+        });
+        test_logviewer("a  |b v", |l| {
+            l.scroll_to_beginning().unwrap();
+            l.scroll_forwards().unwrap();
+        });
+    }
+    #[test]
+    fn test_trailing_newline() {
+        let mut term = FakeTerminal::with_size((3, 3));
+        let mut logviewer = LogViewer::new();
+        write!(logviewer, "a\nb\n").unwrap();
+        assert_eq!(logviewer.num_lines_stored(), 2);
+        logviewer
+            .as_widget()
+            .draw(term.create_root_window(), RenderingHints::default());
+        term.assert_looks_like("   |a  |b  ");
+
+        write!(logviewer, "c").unwrap();
+        assert_eq!(logviewer.num_lines_stored(), 3);
+        logviewer
+            .as_widget()
+            .draw(term.create_root_window(), RenderingHints::default());
+        term.assert_looks_like("a  |b  |c  ");
+    }
+
+    #[test]
+    fn test_crlf() {
+        let mut logviewer = LogViewer::new();
+        write!(logviewer, "a\r\nb\r\nc\r").unwrap();
+        write!(logviewer, "\nd\re").unwrap();
+        assert_eq!(
+            logviewer.view(LineIndex::new(0)..LineIndex::new(4)),
+            &["a", "b", "c", "d\re"]
+        );
     }
 }
@@ -1,12 +1,24 @@
 //! This module contains several basic widgets that are built into the core library.
+pub mod horizontalrule;
+pub mod label;
 pub mod lineedit;
+pub mod linelabel;
 pub mod logviewer;
 pub mod promptline;
+pub mod styledline;
+pub mod tablayout;
 pub mod table;
 pub mod textedit;
+pub mod viewport;
 
+pub use self::horizontalrule::*;
+pub use self::label::*;
 pub use self::lineedit::*;
+pub use self::linelabel::*;
 pub use self::logviewer::*;
 pub use self::promptline::*;
+pub use self::styledline::*;
+pub use self::tablayout::*;
 pub use self::table::*;
 pub use self::textedit::*;
+pub use self::viewport::*;
@@ -0,0 +1,113 @@
+//! A single line of text that consists of differently styled spans.
+use base::basic_types::*;
+use base::{StyleModifier, Window};
+use widget::builtin::LineLabel;
+use widget::{text_width, Demand2D, RenderingHints, Widget};
+
+/// A single line of text that is assembled from spans, each of which is drawn with its own style,
+/// e.g., a keyword highlighted in bold followed by normal text.
+///
+/// A `StyledLine` is a thin builder around a `LineLabel` (which does the drawing). It can be drawn
+/// directly as a widget or turned into a `LineLabel` for further options (secondary text,
+/// alignment, truncation).
+///
+/// # Examples:
+/// ```
+/// use unsegen::base::StyleModifier;
+/// use unsegen::widget::builtin::StyledLine;
+///
+/// let line = StyledLine::new()
+///     .span("error", StyleModifier::new().bold(true))
+///     .span(": file not found", StyleModifier::new());
+/// assert_eq!(line.text(), "error: file not found");
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct StyledLine {
+    label: LineLabel,
+}
+
+impl StyledLine {
+    /// Create an empty line without any spans.
+    pub fn new() -> Self {
+        StyledLine {
+            label: LineLabel::from_segments(Vec::new()),
+        }
+    }
+
+    /// Append a span to the end of the line.
+    pub fn span(mut self, text: impl Into<String>, style: StyleModifier) -> Self {
+        self.push(text, style);
+        self
+    }
+
+    /// Append a span to the end of the line.
+    pub fn push(&mut self, text: impl Into<String>, style: StyleModifier) {
+        self.label.push_segment(text, style);
+    }
+
+    /// Get the spans that make up the line.
+    pub fn spans(&self) -> &[(String, StyleModifier)] {
+        self.label.segments()
+    }
+
+    /// Get the full (unstyled) text of the line.
+    pub fn text(&self) -> String {
+        self.label.text()
+    }
+
+    /// Get the (monospace) width of the line, i.e., the sum of the widths of all spans.
+    pub fn width(&self) -> Width {
+        self.spans().iter().map(|(s, _)| text_width(s)).sum()
+    }
+}
+
+impl Default for StyledLine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<Vec<(String, StyleModifier)>> for StyledLine {
+    fn from(spans: Vec<(String, StyleModifier)>) -> Self {
+        StyledLine {
+            label: LineLabel::from_segments(spans),
+        }
+    }
+}
+
+impl From<StyledLine> for LineLabel {
+    fn from(line: StyledLine) -> Self {
+        line.label
+    }
+}
+
+impl Widget for StyledLine {
+    fn space_demand(&self) -> Demand2D {
+        self.label.space_demand()
+    }
+    fn draw(&self, window: Window, hints: RenderingHints) {
+        self.label.draw(window, hints);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use base::test::FakeTerminal;
+    use widget::Demand;
+
+    #[test]
+    fn test_styled_line() {
+        let line = StyledLine::new()
+            .span("fn", StyleModifier::new().bold(true))
+            .span(" 漢", StyleModifier::new());
+        assert_eq!(line.space_demand().width, Demand::at_least(5));
+
+        let mut term = FakeTerminal::with_size((6, 1));
+        line.draw(term.create_root_window(), RenderingHints::default());
+        term.assert_looks_like("*f**n* 漢 ");
+
+        let label = LineLabel::from(line);
+        assert_eq!(label.text(), "fn 漢");
+    }
+}
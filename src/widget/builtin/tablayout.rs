@@ -0,0 +1,207 @@
+//! A container that shows one of several named widgets below a bar of tabs.
+use super::{LineLabel, Truncation};
+use base::basic_types::*;
+use base::{StyleModifier, Window};
+use input::OperationResult;
+use widget::{
+    layout_proportionally, text_width, Alignment, Demand, Demand2D, RenderingHints, Widget,
+};
+
+/// A container of named widgets of which only the active one is drawn, below a bar listing the
+/// names of all widgets (i.e., tabs).
+///
+/// If the window is too narrow to show all names, each tab receives an equal share of the bar and
+/// names are truncated with an ellipsis. The `RenderingHints` passed to the active widget can be
+/// altered using `WidgetExt::with_hints`.
+///
+/// # Examples:
+/// ```
+/// # use unsegen::base::terminal::test::FakeTerminal;
+/// use unsegen::widget::*;
+/// use unsegen::widget::builtin::TabLayout;
+///
+/// let mut tabs = TabLayout::new()
+///     .tab("one", "first")
+///     .tab("two", "second");
+/// tabs.next_tab().unwrap();
+///
+/// let mut term = FakeTerminal::with_size((10, 2));
+/// tabs.draw(term.create_root_window(), RenderingHints::new());
+/// term.assert_looks_like(" one  two |second    ");
+/// ```
+pub struct TabLayout<'a> {
+    tabs: Vec<(String, Box<dyn Widget + 'a>)>,
+    active: usize,
+    active_tab_style: StyleModifier,
+    inactive_tab_style: StyleModifier,
+}
+
+impl<'a> TabLayout<'a> {
+    /// Create an empty layout without any tabs.
+    pub fn new() -> Self {
+        TabLayout {
+            tabs: Vec::new(),
+            active: 0,
+            active_tab_style: StyleModifier::new().invert(true),
+            inactive_tab_style: StyleModifier::new(),
+        }
+    }
+
+    /// Add a widget as a tab with the given name to the right of all previously added tabs.
+    pub fn tab<W: Widget + 'a>(mut self, name: impl Into<String>, widget: W) -> Self {
+        self.tabs.push((name.into(), Box::new(widget)));
+        self
+    }
+
+    /// Specify the style of the name of the active tab in the bar. (Default: inverted)
+    pub fn active_tab_style(mut self, style: StyleModifier) -> Self {
+        self.active_tab_style = style;
+        self
+    }
+
+    /// Specify the style of the names of all inactive tabs in the bar. (Default: no change)
+    pub fn inactive_tab_style(mut self, style: StyleModifier) -> Self {
+        self.inactive_tab_style = style;
+        self
+    }
+
+    /// Get the index of the active tab.
+    pub fn active_tab(&self) -> usize {
+        self.active
+    }
+
+    /// Get the number of tabs.
+    pub fn num_tabs(&self) -> usize {
+        self.tabs.len()
+    }
+
+    /// Make the tab at the specified index active. An error is returned if there is no such tab.
+    pub fn select_tab(&mut self, index: usize) -> OperationResult {
+        if index < self.tabs.len() {
+            self.active = index;
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+
+    /// Make the tab to the right of the active one active, if there is one.
+    pub fn next_tab(&mut self) -> OperationResult {
+        let index = self.active + 1;
+        self.select_tab(index)
+    }
+
+    /// Make the tab to the left of the active one active, if there is one.
+    pub fn prev_tab(&mut self) -> OperationResult {
+        match self.active.checked_sub(1) {
+            Some(index) => self.select_tab(index),
+            None => Err(()),
+        }
+    }
+
+    fn draw_bar(&self, mut window: Window) {
+        let padded_widths = self
+            .tabs
+            .iter()
+            .map(|(name, _)| text_width(name) + 2)
+            .collect::<Vec<Width>>();
+        let total_width: Width = padded_widths.iter().cloned().sum();
+        let widths = if total_width <= window.get_width() {
+            padded_widths.into_boxed_slice()
+        } else {
+            layout_proportionally(
+                window.get_width(),
+                Width::new(0).unwrap(),
+                &vec![1; self.tabs.len()],
+            )
+        };
+
+        let mut rest = window.create_subwindow(.., ..);
+        for (i, ((name, _), width)) in self.tabs.iter().zip(widths.iter()).enumerate() {
+            let (mut tab_window, r) = rest
+                .split(width.from_origin())
+                .expect("tab widths fit into the bar");
+            rest = r;
+            if i == self.active {
+                tab_window.modify_default_style(self.active_tab_style);
+            } else {
+                tab_window.modify_default_style(self.inactive_tab_style);
+            }
+            tab_window.clear();
+            let mut label = LineLabel::new(name.clone());
+            label.set_alignment(Alignment::Center);
+            label.set_truncation(Truncation::EllipsisEnd);
+            label.draw(tab_window, RenderingHints::default());
+        }
+    }
+}
+
+impl<'a> Widget for TabLayout<'a> {
+    fn space_demand(&self) -> Demand2D {
+        let content = self.tabs.iter().map(|(_, w)| w.space_demand()).fold(
+            Demand2D {
+                width: Demand::zero(),
+                height: Demand::zero(),
+            },
+            |acc, d| Demand2D {
+                width: acc.width.max(d.width),
+                height: acc.height.max(d.height),
+            },
+        );
+        Demand2D {
+            width: content.width,
+            height: content.height + Demand::exact(1),
+        }
+    }
+    fn draw(&self, window: Window, hints: RenderingHints) {
+        let (bar, content) = match window.split(RowIndex::new(1)) {
+            Ok(windows) => windows,
+            Err(_) => return,
+        };
+        self.draw_bar(bar);
+        if let Some((_, widget)) = self.tabs.get(self.active) {
+            widget.draw(content, hints);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use base::test::FakeTerminal;
+
+    fn tabs() -> TabLayout<'static> {
+        TabLayout::new()
+            .tab("ab", "1")
+            .tab("cdef", "22\n22")
+            .active_tab_style(StyleModifier::new().bold(true))
+    }
+
+    #[test]
+    fn test_tab_navigation() {
+        let mut tabs = tabs();
+        assert_eq!(tabs.active_tab(), 0);
+        assert!(tabs.prev_tab().is_err());
+        tabs.next_tab().unwrap();
+        assert!(tabs.next_tab().is_err());
+        assert_eq!(tabs.active_tab(), 1);
+        assert!(tabs.select_tab(2).is_err());
+        tabs.select_tab(0).unwrap();
+        assert_eq!(tabs.active_tab(), 0);
+        assert_eq!(tabs.space_demand().width, Demand::exact(2));
+        assert_eq!(tabs.space_demand().height, Demand::exact(3));
+    }
+
+    #[test]
+    fn test_tab_draw() {
+        let mut tabs = tabs();
+        let mut term = FakeTerminal::with_size((11, 2));
+        tabs.draw(term.create_root_window(), RenderingHints::default());
+        term.assert_looks_like("* **a**b** * cdef  |1          ");
+
+        tabs.next_tab().unwrap();
+        let mut term = FakeTerminal::with_size((6, 3));
+        tabs.draw(term.create_root_window(), RenderingHints::default());
+        term.assert_looks_like("ab *c**d**…*|22    |22    ");
+    }
+}
@@ -7,8 +7,8 @@ use input::Scrollable;
 use input::{Behavior, Input, Navigatable, OperationResult};
 use std::cell::Cell;
 use widget::{
-    layout_linearly, ColDemand, Demand, Demand2D, RenderingHints, RowDemand, SeparatingStyle,
-    Widget,
+    layout_linearly, layout_linearly_with_priorities, ColDemand, Demand, Demand2D, RenderingHints,
+    RowDemand, SeparatingStyle, Widget,
 };
 
 /// A single column in a `Table`.
@@ -177,6 +177,8 @@ impl<R: TableRow + 'static> Table<R> {
             col_sep_style: SeparatingStyle::None,
             focused_style: StyleModifier::new(),
             min_context: 1,
+            column_demands: vec![None; R::num_columns()],
+            shrink_priorities: Vec::new(),
         }
     }
 }
@@ -203,6 +205,8 @@ pub struct TableWidget<'a, R: TableRow + 'static> {
     col_sep_style: SeparatingStyle,
     focused_style: StyleModifier,
     min_context: u32,
+    column_demands: Vec<Option<ColDemand>>,
+    shrink_priorities: Vec<u32>, // Empty if no priority has been specified
 }
 
 impl<'a, R: TableRow + 'static> TableWidget<'a, R> {
@@ -231,6 +235,32 @@ impl<'a, R: TableRow + 'static> TableWidget<'a, R> {
         self
     }
 
+    /// Specify the width demand of a column, replacing the demand computed from its cells. This
+    /// can be used, e.g., to let a description column absorb all extra space while other columns
+    /// keep their size. (Default: max of all cells)
+    pub fn column_demand(mut self, column: usize, demand: ColDemand) -> Self {
+        self.column_demands[column] = Some(demand);
+        self
+    }
+
+    /// Specify the shrink priority of a column. If the table is too narrow to satisfy the minimum
+    /// demands of all columns, columns with a lower priority are truncated first. (Default: 0)
+    ///
+    /// If no priority is specified for any column, space is assigned as in `layout_linearly`.
+    pub fn column_shrink_priority(mut self, column: usize, priority: u32) -> Self {
+        self.shrink_priorities.resize(R::num_columns(), 0);
+        self.shrink_priorities[column] = priority;
+        self
+    }
+
+    fn apply_column_demands(&self, x_demands: &mut [ColDemand]) {
+        for (demand, fixed) in x_demands.iter_mut().zip(self.column_demands.iter()) {
+            if let Some(fixed) = fixed {
+                *demand = *fixed;
+            }
+        }
+    }
+
     fn layout_columns(&self, window: &Window) -> Box<[Width]> {
         let mut x_demands = vec![Demand::zero(); R::num_columns()];
         for row in self.table.rows.iter() {
@@ -239,11 +269,22 @@ impl<'a, R: TableRow + 'static> TableWidget<'a, R> {
                 x_demands[col_num].max_assign(demand2d.width);
             }
         }
+        self.apply_column_demands(&mut x_demands);
         let separator_width = self.col_sep_style.width();
         let weights = std::iter::repeat(1.0)
             .take(x_demands.len())
             .collect::<Vec<f64>>();
-        layout_linearly(window.get_width(), separator_width, &x_demands, &weights)
+        if self.shrink_priorities.is_empty() {
+            layout_linearly(window.get_width(), separator_width, &x_demands, &weights)
+        } else {
+            layout_linearly_with_priorities(
+                window.get_width(),
+                separator_width,
+                &x_demands,
+                &weights,
+                &self.shrink_priorities,
+            )
+        }
     }
 
     fn draw_row<'w>(
@@ -287,13 +328,16 @@ impl<'a, R: TableRow + 'static> TableWidget<'a, R> {
 
             cell_window.clear(); // Fill background using new style
             (col.access)(row).draw(cell_window, cell_draw_hints);
-            if let (Some(_), &SeparatingStyle::Draw(ref c)) = (iter.peek(), &self.col_sep_style) {
-                if window.get_width() > 0 {
-                    let (mut sep_window, r) = window
-                        .split(Width::from(c.width()).from_origin())
-                        .expect("valid split pos from layout");
-                    window = r;
+            let separator_width = self.col_sep_style.width();
+            if iter.peek().is_some() && separator_width > 0 && window.get_width() > 0 {
+                let (mut sep_window, r) = window
+                    .split(separator_width.from_origin())
+                    .expect("valid split pos from layout");
+                window = r;
+                if let SeparatingStyle::Draw(ref c) = self.col_sep_style {
                     sep_window.fill(c.clone());
+                } else {
+                    sep_window.clear();
                 }
             }
         }
@@ -315,6 +359,7 @@ impl<'a, R: TableRow + 'static> TableWidget<'a, R> {
                 y_demand += Demand::exact(self.row_sep_style.height());
             }
         }
+        self.apply_column_demands(&mut x_demands);
 
         //Account all separators between cols
         let x_demand = x_demands.iter().sum::<ColDemand>()
@@ -348,10 +393,13 @@ impl<'a, R: TableRow + 'static> Widget for TableWidget<'a, R> {
             }
         }
 
-        let separator_height = if let &SeparatingStyle::Draw(_) = &self.row_sep_style {
-            Height::new_unchecked(1)
-        } else {
-            Height::new_unchecked(0)
+        let separator_height = self.row_sep_style.height();
+        let fill_separator = |window: &mut Window| {
+            if let SeparatingStyle::Draw(ref c) = self.row_sep_style {
+                window.fill(c.clone());
+            } else {
+                window.clear();
+            }
         };
 
         let max_height = window.get_height();
@@ -427,12 +475,12 @@ impl<'a, R: TableRow + 'static> Widget for TableWidget<'a, R> {
             .enumerate()
             .map(|(i, row)| (i as u32 + current_row_pos + 1, row))
         {
-            if let &SeparatingStyle::Draw(ref c) = &self.row_sep_style {
+            if separator_height > 0 {
                 if let Some(w) = below {
-                    let (mut sep_window, rest) = split_top(w, RowIndex::from(1));
+                    let (mut sep_window, rest) = split_top(w, separator_height.from_origin());
                     below = rest;
 
-                    sep_window.fill(c.clone());
+                    fill_separator(&mut sep_window);
                 } else {
                     break;
                 }
@@ -454,12 +502,12 @@ impl<'a, R: TableRow + 'static> Widget for TableWidget<'a, R> {
             .rev()
             .map(|(i, row)| (i as u32, row))
         {
-            if let &SeparatingStyle::Draw(ref c) = &self.row_sep_style {
+            if separator_height > 0 {
                 if let Some(w) = above {
-                    let (rest, mut sep_window) = split_bottom(w, RowIndex::from(1));
+                    let (rest, mut sep_window) = split_bottom(w, separator_height.from_origin());
                     above = rest;
 
-                    sep_window.fill(c.clone());
+                    fill_separator(&mut sep_window);
                 } else {
                     break;
                 }
@@ -733,4 +781,63 @@ mod test {
             t.row_separation(SeparatingStyle::None)
         });
     }
+
+    struct TwoColumnRow(&'static str, &'static str);
+    impl TableRow for TwoColumnRow {
+        type BehaviorContext = ();
+        const COLUMNS: &'static [Column<Self>] = &[
+            Column {
+                access: |r| Box::new(r.0),
+                behavior: |_, _, _| None,
+            },
+            Column {
+                access: |r| Box::new(r.1),
+                behavior: |_, _, _| None,
+            },
+        ];
+    }
+
+    fn aeq_two_column_draw(
+        terminal_size: (u32, u32),
+        solution: &str,
+        f: impl Fn(TableWidget<TwoColumnRow>) -> TableWidget<TwoColumnRow>,
+    ) {
+        let mut table = Table::new();
+        table.rows_mut().push(TwoColumnRow("ab", "cdef"));
+        let mut term = FakeTerminal::with_size(terminal_size);
+        f(table.as_widget()).draw(term.create_root_window(), RenderingHints::default());
+        term.assert_looks_like(solution);
+    }
+
+    #[test]
+    fn column_demand() {
+        aeq_two_column_draw((8, 1), "abcdef  ", |t| t);
+        aeq_two_column_draw((8, 1), "abcdef  ", |t| {
+            t.column_demand(0, ColDemand::exact(2))
+        });
+        aeq_two_column_draw((8, 1), "ab  cdef", |t| {
+            t.column_demand(0, ColDemand::at_least(2))
+        });
+    }
+
+    #[test]
+    fn column_spacing() {
+        aeq_two_column_draw((9, 1), "ab  cdef ", |t| {
+            t.col_separation(SeparatingStyle::Spacing(2))
+        });
+    }
+
+    #[test]
+    fn column_shrink_priority() {
+        aeq_two_column_draw((4, 1), "abcd", |t| t);
+        aeq_two_column_draw((4, 1), "cdef", |t| t.column_shrink_priority(1, 1));
+
+        // Without explicit priorities, all columns are squeezed as in layout_linearly.
+        fn exact(t: TableWidget<TwoColumnRow>) -> TableWidget<TwoColumnRow> {
+            t.column_demand(0, ColDemand::exact(4))
+                .column_demand(1, ColDemand::exact(4))
+        }
+        aeq_two_column_draw((4, 1), "abcd", exact);
+        aeq_two_column_draw((4, 1), "ab  ", |t| exact(t).column_shrink_priority(0, 1));
+    }
 }
@@ -14,12 +14,6 @@ impl Text {
     fn with_content(s: &str) -> Self {
         Text(Rope::from_str(s))
     }
-    fn clusters_forward(&self, mut pos: TextPosition, n: usize) -> Result<TextPosition, ()> {
-        for _ in 0..n {
-            pos = self.next_grapheme_cluster(pos)?;
-        }
-        Ok(pos)
-    }
     fn next_grapheme_cluster(&self, pos: TextPosition) -> Result<TextPosition, ()> {
         let (mut chunk, mut chunk_begin, _, _) = self.0.chunk_at_byte(pos.0);
         let mut cursor = GraphemeCursor::new(pos.0, self.0.len_bytes(), true);
@@ -66,19 +60,23 @@ impl Text {
             }
         }
     }
-    fn cluster_in_line(&self, pos: TextPosition) -> usize {
+    fn column_in_line(&self, pos: TextPosition) -> Width {
         let begin = self.line_begin(pos);
-        self.count_clusters(begin..pos)
-    }
-    fn count_clusters(&self, mut range: std::ops::Range<TextPosition>) -> usize {
-        assert!(range.start <= range.end);
-        let mut num = 0;
-        while range.start < range.end {
-            range.start = self.next_grapheme_cluster(range.start).unwrap();
-            num += 1;
+        self.slice(begin..pos).text_width()
+    }
+    fn position_at_column(&self, line_begin: TextPosition, column: Width) -> TextPosition {
+        let line_end = self.line_end(line_begin);
+        let mut pos = line_begin;
+        let mut width = Width::new(0).unwrap();
+        while pos < line_end {
+            let next = self.next_grapheme_cluster(pos).unwrap();
+            width += self.slice(pos..next).text_width();
+            if width > column {
+                break;
+            }
+            pos = next;
         }
-        assert_eq!(range.start, range.end);
-        num
+        pos
     }
     fn begin(&self) -> TextPosition {
         TextPosition::begin()
@@ -202,9 +200,13 @@ impl TextPosition {
 ///
 /// In addition to the current text, the `TextEdit` has a concept of a cursor whose position can
 /// change, but is always on a grapheme cluster in the current text.
+///
+/// When moving vertically, the cursor aims for the display column it had after the last horizontal
+/// movement, so that passing through shorter lines does not lose the original column.
 pub struct TextEdit {
     text: Text,
     cursor_pos: TextPosition,
+    goal_column: Option<Width>,
 }
 
 impl TextEdit {
@@ -213,6 +215,7 @@ impl TextEdit {
         TextEdit {
             text: Text::empty(),
             cursor_pos: TextPosition::begin(),
+            goal_column: None,
         }
     }
 
@@ -233,29 +236,34 @@ impl TextEdit {
     /// Move the cursor to the end of the last line.
     pub fn move_cursor_to_end(&mut self) {
         self.cursor_pos = self.text.end();
+        self.goal_column = None;
     }
 
     /// Move the cursor to the end, i.e., *behind* the last grapheme cluster of the current line.
     pub fn move_cursor_to_end_of_line(&mut self) {
         self.cursor_pos = self.text.line_end(self.cursor_pos);
+        self.goal_column = None;
     }
 
     /// Move the cursor to the beginning, i.e., *onto* the first grapheme cluster of the current
     /// line.
     pub fn move_cursor_to_beginning_of_line(&mut self) {
         self.cursor_pos = self.text.line_begin(self.cursor_pos);
+        self.goal_column = None;
+    }
+
+    fn goal_column(&self) -> Width {
+        self.goal_column
+            .unwrap_or_else(|| self.text.column_in_line(self.cursor_pos))
     }
 
     fn move_cursor_down(&mut self) -> Result<(), ()> {
         let line = self.text.line_index(self.cursor_pos);
         if line.raw_value() + 1 < self.text.num_lines() {
-            let pos_in_line = self.text.cluster_in_line(self.cursor_pos);
+            let goal_column = self.goal_column();
             let line_begin = self.text.begin_of_line(line + 1);
-            let line_end = self.text.line_end(line_begin);
-            let num_clusters = self.text.count_clusters(line_begin..line_end);
-
-            let pos_in_line = pos_in_line.min(num_clusters);
-            self.cursor_pos = self.text.clusters_forward(line_begin, pos_in_line).unwrap();
+            self.cursor_pos = self.text.position_at_column(line_begin, goal_column);
+            self.goal_column = Some(goal_column);
 
             Ok(())
         } else {
@@ -268,6 +276,7 @@ impl TextEdit {
             Err(())
         } else {
             self.cursor_pos = self.text.next_grapheme_cluster(self.cursor_pos)?;
+            self.goal_column = None;
             Ok(())
         }
     }
@@ -275,13 +284,10 @@ impl TextEdit {
     fn move_cursor_up(&mut self) -> Result<(), ()> {
         let line = self.text.line_index(self.cursor_pos);
         if line.raw_value() > 0 {
-            let pos_in_line = self.text.cluster_in_line(self.cursor_pos);
+            let goal_column = self.goal_column();
             let line_begin = self.text.begin_of_line(line - 1);
-            let line_end = self.text.line_end(line_begin);
-            let num_clusters = self.text.count_clusters(line_begin..line_end);
-
-            let pos_in_line = pos_in_line.min(num_clusters);
-            self.cursor_pos = self.text.clusters_forward(line_begin, pos_in_line).unwrap();
+            self.cursor_pos = self.text.position_at_column(line_begin, goal_column);
+            self.goal_column = Some(goal_column);
 
             Ok(())
         } else {
@@ -294,6 +300,7 @@ impl TextEdit {
             Err(())
         } else {
             self.cursor_pos = self.text.prev_grapheme_cluster(self.cursor_pos)?;
+            self.goal_column = None;
             Ok(())
         }
     }
@@ -301,6 +308,7 @@ impl TextEdit {
     /// Insert text directly *before* the current cursor position
     pub fn insert(&mut self, text: &str) {
         self.text.insert(self.cursor_pos, text);
+        self.goal_column = None;
     }
 
     /// Returns the byte position of the cursor in the current line
@@ -339,6 +347,7 @@ impl Writable for TextEdit {
     fn write(&mut self, c: char) -> OperationResult {
         self.insert(&c.to_string());
         self.cursor_pos = self.text.next_grapheme_cluster(self.cursor_pos).unwrap();
+        self.goal_column = None;
         Ok(())
     }
 }
@@ -349,6 +358,7 @@ impl Editable for TextEdit {
         let start = self.cursor_pos;
         let end = self.text.next_grapheme_cluster(start)?;
         self.text.remove(start..end);
+        self.goal_column = None;
         Ok(())
     }
     fn delete_backwards(&mut self) -> OperationResult {
@@ -357,6 +367,7 @@ impl Editable for TextEdit {
         let start = self.text.prev_grapheme_cluster(end)?;
         self.text.remove(start..end);
         self.cursor_pos = start;
+        self.goal_column = None;
         Ok(())
     }
     fn go_to_beginning_of_line(&mut self) -> OperationResult {
@@ -373,6 +384,7 @@ impl Editable for TextEdit {
         } else {
             self.text = Text::empty();
             self.cursor_pos = TextPosition::begin();
+            self.goal_column = None;
             Ok(())
         }
     }
@@ -796,4 +808,28 @@ mod test {
             assert!(t.move_cursor_right().is_err());
         });
     }
+
+    #[test]
+    fn test_multi_line_goal_column() {
+        test_textedit((5, 3), "abc*d*_|x____|abcd_", |t| {
+            t.set("abcd\nx\nabcd");
+            t.move_cursor_left().unwrap();
+            t.move_cursor_up().unwrap();
+            t.move_cursor_up().unwrap();
+        });
+
+        test_textedit((5, 3), "*a*bcd_|x____|abcd_", |t| {
+            t.set("abcd\nx\nabcd");
+            t.move_cursor_left().unwrap();
+            t.move_cursor_up().unwrap();
+            t.move_cursor_left().unwrap();
+            t.move_cursor_up().unwrap();
+        });
+
+        test_textedit((5, 2), "a沐*b*_|abcd_", |t| {
+            t.set("a沐b\nabcd");
+            t.move_cursor_left().unwrap();
+            t.move_cursor_up().unwrap();
+        });
+    }
 }
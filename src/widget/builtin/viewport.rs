@@ -0,0 +1,210 @@
+//! A scrollable view onto a widget that is larger than the window it is drawn to.
+use base::basic_types::*;
+use base::{CursorTarget, Window, WindowBuffer};
+use input::{OperationResult, Scrollable};
+use std::cell::Cell;
+use widget::{Demand, Demand2D, RenderingHints, Widget};
+
+/// A scrollable view onto a widget whose minimum demand may exceed the available window.
+///
+/// The inner widget is drawn into an off-screen buffer that is large enough to satisfy its
+/// minimum demand (and at least as large as the window), and the visible region at the current
+/// scroll offset is copied to the window. Vertical scrolling is available via `Scrollable`,
+/// horizontal scrolling via `scroll_left` and `scroll_right`.
+///
+/// The viewport has to be kept across frames to retain the scroll offset.
+///
+/// # Examples:
+/// ```
+/// # use unsegen::base::terminal::test::FakeTerminal;
+/// use unsegen::input::Scrollable;
+/// use unsegen::widget::*;
+/// use unsegen::widget::builtin::Viewport;
+///
+/// let mut viewport = Viewport::new("line 1\nline 2\nline 3");
+///
+/// let mut term = FakeTerminal::with_size((6, 2));
+/// viewport.draw(term.create_root_window(), RenderingHints::new());
+/// term.assert_looks_like("line 1|line 2");
+///
+/// viewport.scroll_forwards().unwrap();
+/// viewport.draw(term.create_root_window(), RenderingHints::new());
+/// term.assert_looks_like("line 2|line 3");
+/// assert!(viewport.scroll_forwards().is_err());
+/// ```
+pub struct Viewport<W: Widget> {
+    widget: W,
+    offset: Cell<(ColIndex, RowIndex)>,
+    window_size: Cell<(Width, Height)>,
+}
+
+impl<W: Widget> Viewport<W> {
+    /// Create a viewport onto the given widget that is scrolled to the top left corner.
+    pub fn new(widget: W) -> Self {
+        Viewport {
+            widget,
+            offset: Cell::new((ColIndex::new(0), RowIndex::new(0))),
+            window_size: Cell::new((Width::new(0).unwrap(), Height::new(0).unwrap())),
+        }
+    }
+
+    /// Get a reference to the inner widget.
+    pub fn inner(&self) -> &W {
+        &self.widget
+    }
+
+    /// Get a mutable reference to the inner widget.
+    pub fn inner_mut(&mut self) -> &mut W {
+        &mut self.widget
+    }
+
+    /// Get the current scroll offset, i.e., the column and row of the inner widget that are drawn
+    /// in the top left corner of the window.
+    pub fn offset(&self) -> (ColIndex, RowIndex) {
+        self.offset.get()
+    }
+
+    /// Set the scroll offset. It is clamped to the size of the inner widget when drawing.
+    pub fn set_offset(&mut self, col: ColIndex, row: RowIndex) {
+        self.offset
+            .set((col.positive_or_zero(), row.positive_or_zero()));
+    }
+
+    /// Scroll one column to the left if possible.
+    pub fn scroll_left(&mut self) -> OperationResult {
+        let (col, row) = self.offset.get();
+        if col > 0 {
+            self.offset.set((col - 1, row));
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+
+    /// Scroll one column to the right if the inner widget extends beyond the right border of the
+    /// window.
+    pub fn scroll_right(&mut self) -> OperationResult {
+        let (col, row) = self.offset.get();
+        if col < self.max_offset().0 {
+            self.offset.set((col + 1, row));
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+
+    /// Get the size of the buffer that the inner widget is drawn to for a window of the given size.
+    fn content_size(&self, window_size: (Width, Height)) -> (Width, Height) {
+        let demand = self.widget.space_demand();
+        (
+            demand.width.min.max(window_size.0),
+            demand.height.min.max(window_size.1),
+        )
+    }
+
+    /// Get the largest possible offset for the window size of the most recent draw.
+    fn max_offset(&self) -> (ColIndex, RowIndex) {
+        let window_size = self.window_size.get();
+        let (content_width, content_height) = self.content_size(window_size);
+        (
+            (content_width - window_size.0).from_origin(),
+            (content_height - window_size.1).from_origin(),
+        )
+    }
+}
+
+impl<W: Widget> Widget for Viewport<W> {
+    fn space_demand(&self) -> Demand2D {
+        let demand = self.widget.space_demand();
+        Demand2D {
+            width: demand.width.max.map_or(Demand::at_least(0), |max| {
+                Demand::from_to(Width::new(0).unwrap(), max)
+            }),
+            height: demand.height.max.map_or(Demand::at_least(0), |max| {
+                Demand::from_to(Height::new(0).unwrap(), max)
+            }),
+        }
+    }
+    fn draw(&self, mut window: Window, hints: RenderingHints) {
+        let window_size = (window.get_width(), window.get_height());
+        self.window_size.set(window_size);
+
+        let (max_col, max_row) = self.max_offset();
+        let (col, row) = self.offset.get();
+        let (col, row) = (col.min(max_col), row.min(max_row));
+        self.offset.set((col, row));
+
+        let (content_width, content_height) = self.content_size(window_size);
+        let mut buffer = WindowBuffer::new(content_width, content_height);
+        let mut content = buffer.as_window();
+        content.set_default_style(*window.default_style());
+        self.widget.draw(content.create_subwindow(.., ..), hints);
+
+        window.for_each_cell(|x, y, cell| {
+            if let Some(source) =
+                content.get_cell(x + col.diff_to_origin(), y + row.diff_to_origin())
+            {
+                *cell = source.clone();
+            }
+        });
+    }
+}
+
+impl<W: Widget> Scrollable for Viewport<W> {
+    fn scroll_backwards(&mut self) -> OperationResult {
+        let (col, row) = self.offset.get();
+        if row > 0 {
+            self.offset.set((col, row - 1));
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+    fn scroll_forwards(&mut self) -> OperationResult {
+        let (col, row) = self.offset.get();
+        if row < self.max_offset().1 {
+            self.offset.set((col, row + 1));
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use base::test::FakeTerminal;
+
+    #[test]
+    fn test_viewport_scroll() {
+        let mut viewport = Viewport::new("abc\ndef\nghi");
+        let draw = |viewport: &Viewport<&str>| {
+            let mut term = FakeTerminal::with_size((2, 2));
+            viewport.draw(term.create_root_window(), RenderingHints::default());
+            term
+        };
+        draw(&viewport).assert_looks_like("ab|de");
+        viewport.scroll_right().unwrap();
+        viewport.scroll_forwards().unwrap();
+        draw(&viewport).assert_looks_like("ef|hi");
+        assert!(viewport.scroll_right().is_err());
+        assert!(viewport.scroll_forwards().is_err());
+        viewport.scroll_to_beginning().unwrap();
+        viewport.scroll_left().unwrap();
+        draw(&viewport).assert_looks_like("ab|de");
+
+        // Offsets beyond the content are clamped.
+        viewport.set_offset(ColIndex::new(5), RowIndex::new(5));
+        draw(&viewport).assert_looks_like("ef|hi");
+        assert_eq!(viewport.offset(), (ColIndex::new(1), RowIndex::new(1)));
+    }
+
+    #[test]
+    fn test_viewport_larger_window() {
+        let viewport = Viewport::new("ab");
+        let mut term = FakeTerminal::from_str((3, 2), "xxx xxx").unwrap();
+        viewport.draw(term.create_root_window(), RenderingHints::default());
+        term.assert_looks_like("ab |   ");
+    }
+}
@@ -1,5 +1,5 @@
 //! Basic linear layouting for `Widget`s.
-use super::{ColDemand, Demand, Demand2D, RenderingHints, RowDemand, Widget};
+use super::{Demand, Demand2D, RenderingHints, Widget};
 use base::basic_types::*;
 use base::{GraphemeCluster, StyleModifier, Window};
 use std::cmp::Ord;
@@ -21,9 +21,74 @@ pub fn layout_linearly<T: AxisDimension + Ord + Debug + Clone>(
     demands: &[Demand<T>],
     weights: &[f64],
 ) -> Box<[PositiveAxisDiff<T>]> {
+    layout_linearly_explained(available_space, separator_width, demands, weights)
+        .iter()
+        .map(|decision| decision.length)
+        .collect::<Vec<_>>()
+        .into_boxed_slice()
+}
+
+/// The reason why `layout_linearly` assigned a certain length to a demand. See `LayoutDecision`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LayoutReason {
+    /// There was not even enough space for the separators, so nothing was assigned.
+    NoSpace,
+    /// There was not enough space to satisfy the minimum demand.
+    BelowMin,
+    /// The minimum demand was assigned (because other demands had a higher priority for the rest
+    /// of the available space).
+    Min,
+    /// The maximum demand was assigned.
+    Max,
+    /// A (weighted) share of the available space between minimum and maximum was assigned.
+    Share,
+}
+
+/// The length assigned to a single demand by `layout_linearly` and the reasoning behind it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LayoutDecision<T: AxisDimension> {
+    /// The assigned length.
+    pub length: PositiveAxisDiff<T>,
+    /// Why this length was assigned.
+    pub reason: LayoutReason,
+    /// The number of cells (included in `length`) that were assigned when distributing space left
+    /// over by rounding.
+    pub remainder_cells: u32,
+}
+
+/// Compute assigned lengths exactly as `layout_linearly` does, but additionally report why each
+/// demand received its length.
+///
+/// This is useful for debugging layouts or writing tests that check the reasoning of the
+/// algorithm.
+///
+/// # Examples:
+/// ```
+/// use unsegen::base::*;
+/// use unsegen::widget::*;
+///
+/// let decisions = layout_linearly_explained(
+///     Width::new(10).unwrap(),
+///     Width::new(0).unwrap(),
+///     &[ColDemand::exact(2), ColDemand::from_to(1, 3), ColDemand::at_least(4)],
+///     &[1.0, 1.0, 1.0],
+/// );
+/// assert_eq!(decisions[0].reason, LayoutReason::Max);
+/// assert_eq!(decisions[1].reason, LayoutReason::Max);
+/// assert_eq!(decisions[2].reason, LayoutReason::Share);
+/// assert_eq!(decisions[2].length, Width::new(5).unwrap());
+/// ```
+pub fn layout_linearly_explained<T: AxisDimension + Ord + Debug + Clone>(
+    available_space: PositiveAxisDiff<T>,
+    separator_width: PositiveAxisDiff<T>,
+    demands: &[Demand<T>],
+    weights: &[f64],
+) -> Box<[LayoutDecision<T>]> {
     //eprintln!("av {}, sep {}, dem, {:?}", available_space, separator_width, demands);
     assert_eq!(demands.len(), weights.len());
 
+    let original_demands = demands;
+
     let mut assigned_spaces = vec![0.0; demands.len()].into_boxed_slice();
 
     struct DemandF {
@@ -42,7 +107,12 @@ pub fn layout_linearly<T: AxisDimension + Ord + Debug + Clone>(
     // Reserve space for separators
     let diff = available_space - separator_width * demands.len().saturating_sub(1);
     if diff < 0 {
-        return vec![PositiveAxisDiff::new(0).unwrap(); demands.len()].into_boxed_slice();
+        let no_space = LayoutDecision {
+            length: PositiveAxisDiff::new(0).unwrap(),
+            reason: LayoutReason::NoSpace,
+            remainder_cells: 0,
+        };
+        return vec![no_space; demands.len()].into_boxed_slice();
     }
 
     let total = diff.try_into_positive().unwrap().raw_value() as f64;
@@ -178,6 +248,7 @@ pub fn layout_linearly<T: AxisDimension + Ord + Debug + Clone>(
     let mut still_to_assign = (diff - total_assigned).min(total_demand);
 
     // Distribute spaces accumulated through rounding errors
+    let mut remainder_cells = vec![0; demands.len()];
     {
         // Collect not completely fulfilled rewards
         let mut unfinished = (0..demands.len())
@@ -201,6 +272,7 @@ pub fn layout_linearly<T: AxisDimension + Ord + Debug + Clone>(
 
                 *s += 1;
                 still_to_assign -= 1;
+                remainder_cells[i] += 1;
 
                 if s.raw_value() < demand.max as i32 {
                     still_unfinished.push(i);
@@ -210,65 +282,288 @@ pub fn layout_linearly<T: AxisDimension + Ord + Debug + Clone>(
         }
     }
 
-    assigned_int.into_boxed_slice()
+    assigned_int
+        .into_iter()
+        .zip(original_demands.iter())
+        .zip(remainder_cells)
+        .map(|((length, demand), remainder_cells)| {
+            let reason = if length < demand.min {
+                LayoutReason::BelowMin
+            } else if Some(length) == demand.max {
+                LayoutReason::Max
+            } else if length == demand.min {
+                LayoutReason::Min
+            } else {
+                LayoutReason::Share
+            };
+            LayoutDecision {
+                length,
+                reason,
+                remainder_cells,
+            }
+        })
+        .collect::<Vec<_>>()
+        .into_boxed_slice()
+}
+
+/// Compute assigned lengths like `layout_linearly`, but control which demands are shrunk below
+/// their minimum first if there is not enough space to satisfy all minimum demands.
+///
+/// Demands with a lower `shrink_priorities` value are shrunk first (down to zero, if necessary)
+/// before demands with a higher value are touched. Among demands with the same priority, the
+/// rightmost one is shrunk first. If all minimum demands can be satisfied, the result is identical
+/// to that of `layout_linearly`.
+///
+/// # Examples:
+/// ```
+/// use unsegen::base::*;
+/// use unsegen::widget::*;
+///
+/// let lengths = layout_linearly_with_priorities(
+///     Width::new(5).unwrap(),
+///     Width::new(0).unwrap(),
+///     &[ColDemand::exact(4), ColDemand::exact(4)],
+///     &[1.0, 1.0],
+///     &[0, 1],
+/// );
+/// assert_eq!(lengths[0], 1);
+/// assert_eq!(lengths[1], 4);
+/// ```
+pub fn layout_linearly_with_priorities<T: AxisDimension + Ord + Debug + Clone>(
+    available_space: PositiveAxisDiff<T>,
+    separator_width: PositiveAxisDiff<T>,
+    demands: &[Demand<T>],
+    weights: &[f64],
+    shrink_priorities: &[u32],
+) -> Box<[PositiveAxisDiff<T>]> {
+    assert_eq!(demands.len(), shrink_priorities.len());
+
+    let space_for_demands = available_space - separator_width * demands.len().saturating_sub(1);
+    let total_min: PositiveAxisDiff<T> = demands.iter().map(|d| d.min).sum();
+    let mut overflow = match (total_min - space_for_demands).try_into_positive() {
+        Ok(overflow) if overflow > 0 && space_for_demands >= 0 => overflow,
+        _ => return layout_linearly(available_space, separator_width, demands, weights),
+    };
+
+    let mut lengths = demands.iter().map(|d| d.min).collect::<Vec<_>>();
+    let mut shrink_order = (0..demands.len()).rev().collect::<Vec<_>>();
+    shrink_order.sort_by_key(|&i| shrink_priorities[i]);
+    for i in shrink_order {
+        let shrink = lengths[i].min(overflow);
+        lengths[i] = (lengths[i] - shrink).positive_or_zero();
+        overflow = (overflow - shrink).positive_or_zero();
+    }
+    lengths.into_boxed_slice()
+}
+
+/// Split `available_space` (minus the separators between the parts) into parts whose lengths are
+/// proportional to the given (integer) `ratios`, regardless of any demands.
+///
+/// Columns that are left over due to rounding are assigned one by one to the first parts with a
+/// non-zero ratio. If all ratios are zero, they are treated as equal. The lengths always sum up to
+/// the available space minus separators (or zero if there is not even enough space for the
+/// separators).
+///
+/// # Examples:
+/// ```
+/// use unsegen::base::*;
+/// use unsegen::widget::*;
+///
+/// let lengths = layout_proportionally(Width::new(10).unwrap(), Width::new(0).unwrap(), &[1, 2, 1]);
+/// assert_eq!(lengths[0], 3);
+/// assert_eq!(lengths[1], 5);
+/// assert_eq!(lengths[2], 2);
+/// ```
+pub fn layout_proportionally<T: AxisDimension + Ord + Debug + Clone>(
+    available_space: PositiveAxisDiff<T>,
+    separator_width: PositiveAxisDiff<T>,
+    ratios: &[u32],
+) -> Box<[PositiveAxisDiff<T>]> {
+    let space = (available_space - separator_width * ratios.len().saturating_sub(1))
+        .positive_or_zero()
+        .raw_value() as u64;
+    let ratios = if ratios.iter().all(|&r| r == 0) {
+        vec![1; ratios.len()]
+    } else {
+        ratios.to_vec()
+    };
+    let total_ratio: u64 = ratios.iter().map(|&r| r as u64).sum();
+
+    let mut lengths = ratios
+        .iter()
+        .map(|&r| space * r as u64 / total_ratio)
+        .collect::<Vec<_>>();
+    let mut left_over = space - lengths.iter().sum::<u64>();
+    for (length, &ratio) in lengths.iter_mut().zip(ratios.iter()) {
+        if left_over == 0 {
+            break;
+        }
+        if ratio > 0 {
+            *length += 1;
+            left_over -= 1;
+        }
+    }
+    lengths
+        .into_iter()
+        .map(|l| PositiveAxisDiff::new_unchecked(l as i32))
+        .collect::<Vec<_>>()
+        .into_boxed_slice()
+}
+
+/// Options of a linear layout (i.e., `HLayout` or `VLayout`) along the dimension `T`.
+struct LinearLayoutOptions<T: AxisDimension> {
+    /// Separation of all gaps between widgets that have no individual separator
+    separating_style: SeparatingStyle,
+    /// Individual separator for the gap after the i-th widget
+    separators: Vec<GraphemeCluster>,
+    /// Fixed ratios (one per widget) that replace demands and weights
+    ratios: Option<Vec<u32>>,
+    /// Shrink priority of the i-th widget (0 if missing). Empty if no priority was specified.
+    shrink_priorities: Vec<u32>,
+    /// Minimum length (one per widget) below which a widget is hidden
+    min_useful: Vec<Option<PositiveAxisDiff<T>>>,
+    /// Place the first widget at the end of the window
+    reversed: bool,
+}
+
+impl<T: AxisDimension> LinearLayoutOptions<T> {
+    fn new() -> Self {
+        LinearLayoutOptions {
+            separating_style: SeparatingStyle::None,
+            separators: Vec::new(),
+            ratios: None,
+            shrink_priorities: Vec::new(),
+            min_useful: Vec::new(),
+            reversed: false,
+        }
+    }
 }
 
-/// Draw the widgets in the given window in a linear layout.
-fn draw_linearly<'a, T: AxisDimension + Ord + Debug + Copy, S, L, M, D>(
-    window: Window,
+/// Draw the widgets in the given window in a linear layout along the dimension `T`.
+///
+/// The gap after the i-th widget is filled with `separators[i]` if present and according to
+/// `separating_style` otherwise. Widgets without a corresponding entry in `shrink_priorities` have
+/// a shrink priority of 0.
+///
+/// Widgets that are assigned less space than their `min_useful` length are not drawn at all (and
+/// neither is their separator). Their space is redistributed among the remaining widgets.
+///
+/// If `reversed` is set, the first widget is placed at the end (i.e., right or bottom) of the
+/// window and the following widgets are placed towards its start. Space is assigned exactly as in
+/// the regular order.
+fn draw_linearly<'a, 'w, T: AxisDimension + Ord + Debug + Copy, D>(
+    window: Window<'w>,
     widgets: &[Box<dyn Widget + 'a>],
     weights: &[f64],
-    rendering_hints: &[RenderingHints],
-    separating_style: &SeparatingStyle,
-    split: S,
-    window_length: L,
-    separator_length: M,
+    hints: RenderingHints,
+    options: &LinearLayoutOptions<T>,
+    separator_length: PositiveAxisDiff<T>,
     demand_dimension: D,
 ) where
-    S: Fn(Window, AxisIndex<T>) -> (Window, Window),
-    L: Fn(&Window) -> PositiveAxisDiff<T>,
-    M: Fn(&SeparatingStyle) -> PositiveAxisDiff<T>,
     D: Fn(Demand2D) -> Demand<T>,
 {
+    let LinearLayoutOptions {
+        ref separating_style,
+        ref separators,
+        ref ratios,
+        ref shrink_priorities,
+        ref min_useful,
+        reversed,
+    } = *options;
+    let split = |window: Window<'w>, pos: AxisIndex<T>| -> (Window<'w>, Window<'w>) {
+        window.split(pos).expect("valid split pos")
+    };
+    let window_length = |window: &Window| window.get_extent::<T>();
     assert_eq!(widgets.len(), weights.len());
-    assert_eq!(widgets.len(), rendering_hints.len());
-    let separator_length = separator_length(separating_style);
+    assert_eq!(widgets.len(), min_useful.len());
+    if let Some(ratios) = ratios {
+        assert_eq!(
+            widgets.len(),
+            ratios.len(),
+            "widgets and ratios len mismatch"
+        );
+    }
     let demands: Vec<Demand<T>> = widgets
         .iter()
         .map(|w| demand_dimension(w.space_demand()))
         .collect();
-    let assigned_spaces = layout_linearly(
-        window_length(&window),
-        separator_length,
-        demands.as_slice(),
-        weights,
-    );
+    let assign_spaces = |visible: &[usize]| {
+        if let Some(ratios) = ratios {
+            let ratios = visible.iter().map(|&i| ratios[i]).collect::<Vec<_>>();
+            return layout_proportionally(window_length(&window), separator_length, &ratios);
+        }
+        let demands = visible.iter().map(|&i| demands[i]).collect::<Vec<_>>();
+        let weights = visible.iter().map(|&i| weights[i]).collect::<Vec<_>>();
+        if shrink_priorities.is_empty() {
+            layout_linearly(window_length(&window), separator_length, &demands, &weights)
+        } else {
+            let shrink_priorities = visible
+                .iter()
+                .map(|&i| shrink_priorities.get(i).cloned().unwrap_or(0))
+                .collect::<Vec<_>>();
+            layout_linearly_with_priorities(
+                window_length(&window),
+                separator_length,
+                &demands,
+                &weights,
+                &shrink_priorities,
+            )
+        }
+    };
+
+    // Hide widgets that are too small to be useful one by one (starting with the last one) until
+    // all remaining widgets receive enough space.
+    let mut visible = (0..widgets.len()).collect::<Vec<_>>();
+    let mut assigned_spaces = assign_spaces(&visible);
+    while let Some(pos) = visible
+        .iter()
+        .zip(assigned_spaces.iter())
+        .rposition(|(&i, &space)| matches!(min_useful[i], Some(min) if space < min))
+    {
+        visible.remove(pos);
+        assigned_spaces = assign_spaces(&visible);
+    }
 
     debug_assert!(
-        widgets.len() == assigned_spaces.len(),
+        visible.len() == assigned_spaces.len(),
         "widgets and spaces len mismatch"
     );
 
+    // Split off the given length for the next widget (or separator) from the rest of the window.
+    let split_off = |window: Window<'w>, length: PositiveAxisDiff<T>| -> (Window<'w>, Window<'w>) {
+        if reversed {
+            let start = (window_length(&window) - length).positive_or_zero();
+            let (rest, part) = split(window, start.from_origin());
+            (part, rest)
+        } else {
+            split(window, length.from_origin())
+        }
+    };
+
     let mut rest_window = window;
-    let mut iter = widgets
+    let mut iter = visible
         .iter()
-        .zip(rendering_hints.iter())
         .zip(assigned_spaces.iter())
         .enumerate()
         .peekable();
-    while let Some((i, ((w, hint), &pos))) = iter.next() {
-        let (mut window, r) = split(rest_window, pos.from_origin());
+    while let Some((n, (&i, &pos))) = iter.next() {
+        let (mut window, r) = split_off(rest_window, pos);
         rest_window = r;
-        if let (1, &SeparatingStyle::AlternatingStyle(modifier)) = (i % 2, separating_style) {
+        // The style alternates with respect to the visual (not the logical) order.
+        let visual_index = if reversed { visible.len() - 1 - n } else { n };
+        if let (1, &SeparatingStyle::AlternatingStyle(modifier)) =
+            (visual_index % 2, separating_style)
+        {
             window.modify_default_style(modifier);
         }
         window.clear(); // Fill background using new style
-        w.draw(window, *hint);
-        if let (Some(_), &SeparatingStyle::Draw(ref c)) = (iter.peek(), separating_style) {
-            if window_length(&rest_window) > 0 {
-                let (mut window, r) = split(rest_window, separator_length.from_origin());
-                rest_window = r;
-                window.fill(c.clone());
+        widgets[i].draw(window, hints);
+        if iter.peek().is_some() && separator_length > 0 && window_length(&rest_window) > 0 {
+            let (mut window, r) = split_off(rest_window, separator_length);
+            rest_window = r;
+            match (separators.get(i), separating_style) {
+                (Some(c), _) | (None, SeparatingStyle::Draw(c)) => window.fill(c.clone()),
+                _ => window.clear(),
             }
         }
     }
@@ -281,25 +576,58 @@ fn draw_linearly<'a, T: AxisDimension + Ord + Debug + Copy, S, L, M, D>(
 /// weights) to the widgets. Please note that these are not absolute: For example, the minimum
 /// demand of all widgets will be met before *then* assigning additional space according to weights
 /// again.
+///
+/// Widgets of different types (owned, borrowed or already boxed) can be mixed freely, as the layout
+/// stores them as trait objects internally. The `RenderingHints` passed to individual widgets can
+/// be altered using `WidgetExt::with_hints`. The layout is meant to be rebuilt for every frame.
+///
+/// # Examples:
+/// ```
+/// # use unsegen::base::terminal::test::FakeTerminal;
+/// use unsegen::widget::*;
+/// use unsegen::widget::builtin::*;
+///
+/// let edit = LineEdit::new();
+/// let panes: Vec<Box<dyn Widget>> = vec![Box::new("foo"), Box::new(LineLabel::new("bar"))];
+///
+/// let mut layout = HLayout::new().widget(edit.as_widget().with_hints(|h| h.active(false)));
+/// for pane in panes {
+///     layout = layout.widget(pane);
+/// }
+///
+/// # let mut term = FakeTerminal::with_size((10, 1));
+/// layout.draw(term.create_root_window(), RenderingHints::new().active(true));
+/// ```
 pub struct HLayout<'a> {
-    separating_style: SeparatingStyle,
     widgets: Vec<Box<dyn Widget + 'a>>,
     weights: Vec<f64>,
+    options: LinearLayoutOptions<ColDimension>,
 }
 
 impl<'a> HLayout<'a> {
     /// Create an empty widget with no separation of added widgets by default.
     pub fn new() -> Self {
         HLayout {
-            separating_style: SeparatingStyle::None,
             widgets: Vec::new(),
             weights: Vec::new(),
+            options: LinearLayoutOptions::new(),
         }
     }
 
+    /// Create an empty widget that splits the available space between the added widgets according
+    /// to the given fixed ratios (e.g., `&[1, 2, 1]`), regardless of their demands and weights.
+    /// See `layout_proportionally`.
+    ///
+    /// Exactly one ratio has to be specified for every widget that is added.
+    pub fn with_ratios(ratios: &[u32]) -> Self {
+        let mut layout = Self::new();
+        layout.options.ratios = Some(ratios.to_vec());
+        layout
+    }
+
     /// Specify the style for (horizontal) separation of added widgets.
     pub fn separating_style(mut self, style: SeparatingStyle) -> Self {
-        self.separating_style = style;
+        self.options.separating_style = style;
         self
     }
 
@@ -315,6 +643,97 @@ impl<'a> HLayout<'a> {
         self.separating_style(SeparatingStyle::AlternatingStyle(style_modifier))
     }
 
+    /// Specify individual separators for the gaps between widgets: The first separator is drawn
+    /// between the first and second widget, and so on. Gaps without a corresponding separator are
+    /// drawn according to the `SeparatingStyle`.
+    ///
+    /// All gaps are as wide as the widest separator.
+    ///
+    /// # Examples:
+    /// ```
+    /// # use unsegen::base::terminal::test::FakeTerminal;
+    /// use unsegen::base::GraphemeCluster;
+    /// use unsegen::widget::*;
+    ///
+    /// let layout = HLayout::new()
+    ///     .separator(GraphemeCluster::try_from('|').unwrap())
+    ///     .separators(&[GraphemeCluster::try_from('#').unwrap()])
+    ///     .widget("a")
+    ///     .widget("b")
+    ///     .widget("c");
+    ///
+    /// let mut term = FakeTerminal::with_size((5, 1));
+    /// layout.draw(term.create_root_window(), RenderingHints::new());
+    /// assert_eq!(term, FakeTerminal::from_str((5, 1), "a#b|c").unwrap());
+    /// ```
+    pub fn separators(mut self, separators: &[GraphemeCluster]) -> Self {
+        self.options.separators = separators.to_vec();
+        self
+    }
+
+    fn separator_width(&self) -> Width {
+        self.options
+            .separators
+            .iter()
+            .map(|c| Width::from(c.width()))
+            .fold(self.options.separating_style.width(), ::std::cmp::max)
+    }
+
+    /// Specify which widgets are shrunk below their minimum demand first if the window is too
+    /// narrow for all of them: The i-th priority belongs to the i-th widget, and widgets with a
+    /// lower priority are shrunk first. Widgets without a corresponding priority have a priority of
+    /// 0. See `layout_linearly_with_priorities`.
+    ///
+    /// Among widgets with the same priority, the rightmost one is shrunk first. If no priorities
+    /// are specified, space is assigned as in `layout_linearly`.
+    ///
+    /// # Examples:
+    /// ```
+    /// # use unsegen::base::terminal::test::FakeTerminal;
+    /// use unsegen::widget::*;
+    ///
+    /// let layout = HLayout::new()
+    ///     .shrink_priorities(&[0, 1])
+    ///     .widget("abc")
+    ///     .widget("def");
+    ///
+    /// let mut term = FakeTerminal::with_size((4, 1));
+    /// layout.draw(term.create_root_window(), RenderingHints::new());
+    /// term.assert_looks_like("adef");
+    /// ```
+    pub fn shrink_priorities(mut self, priorities: &[u32]) -> Self {
+        self.options.shrink_priorities = priorities.to_vec();
+        self
+    }
+
+    /// Lay out the widgets from right to left instead, i.e., the first widget is placed at the
+    /// right border of the window. This is useful to anchor widgets (e.g., in a status bar) to the
+    /// right.
+    ///
+    /// The gap after the i-th widget (see `separators`) is then located to its left. Space is
+    /// assigned exactly as in the regular order, so the first widget still takes precedence.
+    ///
+    /// # Examples:
+    /// ```
+    /// # use unsegen::base::terminal::test::FakeTerminal;
+    /// use unsegen::base::GraphemeCluster;
+    /// use unsegen::widget::*;
+    ///
+    /// let layout = HLayout::new()
+    ///     .reversed()
+    ///     .separator(GraphemeCluster::try_from(',').unwrap())
+    ///     .widget("12:00")
+    ///     .widget("42%");
+    ///
+    /// let mut term = FakeTerminal::with_size((12, 1));
+    /// layout.draw(term.create_root_window(), RenderingHints::new());
+    /// term.assert_looks_like("   42%,12:00");
+    /// ```
+    pub fn reversed(mut self) -> Self {
+        self.options.reversed = true;
+        self
+    }
+
     /// Add a widget to the list of widgets to be layed out. It will be placed to the right of all
     /// previously added widgets.
     ///
@@ -328,42 +747,62 @@ impl<'a> HLayout<'a> {
     pub fn widget_weighted<W: Widget + 'a>(mut self, t: W, weight: f64) -> Self {
         self.widgets.push(Box::new(t));
         self.weights.push(weight);
+        self.options.min_useful.push(None);
+        self
+    }
+
+    /// Add a widget that is hidden entirely (instead of being drawn clipped) if it would be assigned
+    /// less than `min_useful` columns. The space of a hidden widget is distributed among the
+    /// remaining widgets. It will be placed to the right of all previously added widgets.
+    ///
+    /// If several widgets are too narrow, they are hidden one by one starting with the rightmost
+    /// one. Since the widget may vanish, it does not contribute to the minimum width of the layout.
+    ///
+    /// # Examples:
+    /// ```
+    /// # use unsegen::base::terminal::test::FakeTerminal;
+    /// use unsegen::base::Width;
+    /// use unsegen::widget::*;
+    ///
+    /// let layout = HLayout::new()
+    ///     .widget("main.rs")
+    ///     .widget_hideable("[save]", Width::new(6).unwrap());
+    ///
+    /// let mut term = FakeTerminal::with_size((10, 1));
+    /// layout.draw(term.create_root_window(), RenderingHints::new());
+    /// term.assert_looks_like("main.rs   ");
+    /// ```
+    pub fn widget_hideable<W: Widget + 'a>(mut self, t: W, min_useful: Width) -> Self {
+        self = self.widget(t);
+        *self.options.min_useful.last_mut().unwrap() = Some(min_useful);
         self
     }
 }
 
 impl<'a> Widget for HLayout<'a> {
     fn space_demand(&self) -> Demand2D {
-        let mut total_x = ColDemand::exact(0);
-        let mut total_y = RowDemand::exact(0);
-        let mut n_elements = 0;
-        for w in self.widgets.iter() {
-            let demand2d = w.space_demand();
-            total_x = total_x + demand2d.width;
-            total_y = total_y.max(demand2d.height);
-            n_elements += 1;
-        }
-        if let SeparatingStyle::Draw(_) = self.separating_style {
-            total_x += Demand::exact(n_elements);
-        }
-        Demand2D {
-            width: total_x,
-            height: total_y,
-        }
+        let demands = self
+            .widgets
+            .iter()
+            .zip(self.options.min_useful.iter())
+            .map(|(w, min_useful)| {
+                let mut demand = w.space_demand();
+                if min_useful.is_some() {
+                    demand.width.min = Width::new(0).unwrap();
+                }
+                demand
+            })
+            .collect::<Vec<_>>();
+        Demand2D::aggregate_horizontal(&demands, self.separator_width())
     }
     fn draw(&self, window: Window, hints: RenderingHints) {
-        let hints = std::iter::repeat(hints)
-            .take(self.widgets.len())
-            .collect::<Vec<_>>();
         draw_linearly(
             window,
             &self.widgets,
             &self.weights,
-            &hints,
-            &self.separating_style,
-            |w, p| w.split(p).expect("valid split pos"),
-            |w| w.get_width(),
-            SeparatingStyle::width,
+            hints,
+            &self.options,
+            self.separator_width(),
             |d| d.width,
         );
     }
@@ -377,25 +816,36 @@ impl<'a> Widget for HLayout<'a> {
 /// demand of all widgets will be met before *then* assigning additional space according to weights
 /// again.
 pub struct VLayout<'a> {
-    separating_style: SeparatingStyle,
     widgets: Vec<Box<dyn Widget + 'a>>,
     weights: Vec<f64>,
+    options: LinearLayoutOptions<RowDimension>,
 }
 
 impl<'a> VLayout<'a> {
     /// Create an empty widget with no separation of added widgets by default.
     pub fn new() -> Self {
         VLayout {
-            separating_style: SeparatingStyle::None,
             widgets: Vec::new(),
             weights: Vec::new(),
+            options: LinearLayoutOptions::new(),
         }
     }
 
+    /// Create an empty widget that splits the available space between the added widgets according
+    /// to the given fixed ratios (e.g., `&[1, 2, 1]`), regardless of their demands and weights.
+    /// See `layout_proportionally`.
+    ///
+    /// Exactly one ratio has to be specified for every widget that is added.
+    pub fn with_ratios(ratios: &[u32]) -> Self {
+        let mut layout = Self::new();
+        layout.options.ratios = Some(ratios.to_vec());
+        layout
+    }
+
     /// Make widgets be separated by the given GraphemeCluster (convenience wrapper around
     /// `separating_style` method and specifying SeparatingStyle::Draw).
     pub fn separating_style(mut self, style: SeparatingStyle) -> Self {
-        self.separating_style = style;
+        self.options.separating_style = style;
         self
     }
 
@@ -410,6 +860,47 @@ impl<'a> VLayout<'a> {
         self.separating_style(SeparatingStyle::AlternatingStyle(style_modifier))
     }
 
+    /// Specify individual separators for the gaps between widgets: The first separator is drawn
+    /// between the first and second widget, and so on. Gaps without a corresponding separator are
+    /// drawn according to the `SeparatingStyle`.
+    pub fn separators(mut self, separators: &[GraphemeCluster]) -> Self {
+        self.options.separators = separators.to_vec();
+        self
+    }
+
+    fn separator_height(&self) -> Height {
+        if self.options.separators.is_empty() {
+            self.options.separating_style.height()
+        } else {
+            ::std::cmp::max(
+                self.options.separating_style.height(),
+                Height::new(1).unwrap(),
+            )
+        }
+    }
+
+    /// Specify which widgets are shrunk below their minimum demand first if the window is too low
+    /// for all of them: The i-th priority belongs to the i-th widget, and widgets with a lower
+    /// priority are shrunk first. Widgets without a corresponding priority have a priority of 0.
+    /// See `layout_linearly_with_priorities`.
+    ///
+    /// Among widgets with the same priority, the bottommost one is shrunk first. If no priorities
+    /// are specified, space is assigned as in `layout_linearly`.
+    pub fn shrink_priorities(mut self, priorities: &[u32]) -> Self {
+        self.options.shrink_priorities = priorities.to_vec();
+        self
+    }
+
+    /// Lay out the widgets from bottom to top instead, i.e., the first widget is placed at the
+    /// bottom border of the window.
+    ///
+    /// The gap after the i-th widget (see `separators`) is then located above it. Space is
+    /// assigned exactly as in the regular order, so the first widget still takes precedence.
+    pub fn reversed(mut self) -> Self {
+        self.options.reversed = true;
+        self
+    }
+
     /// Add a widget to the list of widgets to be layed out. It will be placed below all previously
     /// added widgets.
     ///
@@ -423,49 +914,116 @@ impl<'a> VLayout<'a> {
     pub fn widget_weighted<W: Widget + 'a>(mut self, t: W, weight: f64) -> Self {
         self.widgets.push(Box::new(t));
         self.weights.push(weight);
+        self.options.min_useful.push(None);
+        self
+    }
+
+    /// Add a widget that is hidden entirely (instead of being drawn clipped) if it would be assigned
+    /// less than `min_useful` rows. The space of a hidden widget is distributed among the
+    /// remaining widgets. It will be placed below all previously added widgets.
+    ///
+    /// If several widgets are too low, they are hidden one by one starting with the bottommost
+    /// one. Since the widget may vanish, it does not contribute to the minimum height of the
+    /// layout.
+    pub fn widget_hideable<W: Widget + 'a>(mut self, t: W, min_useful: Height) -> Self {
+        self = self.widget(t);
+        *self.options.min_useful.last_mut().unwrap() = Some(min_useful);
         self
     }
 }
 
 impl<'a> Widget for VLayout<'a> {
     fn space_demand(&self) -> Demand2D {
-        let mut total_x = Demand::exact(0);
-        let mut total_y = Demand::exact(0);
-        let mut n_elements = 0;
-        for w in self.widgets.iter() {
-            let demand2d = w.space_demand();
-            total_x = total_x.max(demand2d.width);
-            total_y = total_y + demand2d.height;
-            n_elements += 1;
-        }
-        if let SeparatingStyle::Draw(_) = self.separating_style {
-            total_y = total_y + Demand::exact(n_elements);
-        }
-        Demand2D {
-            width: total_x,
-            height: total_y,
-        }
+        let demands = self
+            .widgets
+            .iter()
+            .zip(self.options.min_useful.iter())
+            .map(|(w, min_useful)| {
+                let mut demand = w.space_demand();
+                if min_useful.is_some() {
+                    demand.height.min = Height::new(0).unwrap();
+                }
+                demand
+            })
+            .collect::<Vec<_>>();
+        Demand2D::aggregate_vertical(&demands, self.separator_height())
     }
 
     /// Draw the given widgets to the window, from top to bottom.
     fn draw(&self, window: Window, hints: RenderingHints) {
-        let hints = std::iter::repeat(hints)
-            .take(self.widgets.len())
-            .collect::<Vec<_>>();
         draw_linearly(
             window,
             &self.widgets,
             &self.weights,
-            &hints,
-            &self.separating_style,
-            |w, p| w.split(p).expect("valid split pos"),
-            |w| w.get_height(),
-            SeparatingStyle::height,
+            hints,
+            &self.options,
+            self.separator_height(),
             |d| d.height,
         );
     }
 }
 
+/// A wrapper widget that draws a set of widgets on top of each other into the same window.
+///
+/// Widgets are drawn in the order they are added, so later widgets paint over earlier ones. This
+/// is useful for overlays such as popups or notifications on top of a base widget. Note that
+/// widgets only overwrite the cells they actually draw to. The `RenderingHints` passed to
+/// individual widgets can be altered using `WidgetExt::with_hints`.
+///
+/// # Examples:
+/// ```
+/// # use unsegen::base::terminal::test::FakeTerminal;
+/// use unsegen::widget::*;
+///
+/// let layout = StackLayout::new()
+///     .widget("base text")
+///     .widget("popup".centered());
+///
+/// let mut term = FakeTerminal::with_size((9, 1));
+/// layout.draw(term.create_root_window(), RenderingHints::new());
+/// term.assert_looks_like("bapopupxt");
+/// ```
+pub struct StackLayout<'a> {
+    widgets: Vec<Box<dyn Widget + 'a>>,
+}
+
+impl<'a> StackLayout<'a> {
+    /// Create an empty layout.
+    pub fn new() -> Self {
+        StackLayout {
+            widgets: Vec::new(),
+        }
+    }
+
+    /// Add a widget that will be drawn on top of all previously added widgets.
+    pub fn widget<W: Widget + 'a>(mut self, t: W) -> Self {
+        self.widgets.push(Box::new(t));
+        self
+    }
+}
+
+impl<'a> Widget for StackLayout<'a> {
+    fn space_demand(&self) -> Demand2D {
+        self.widgets.iter().map(|w| w.space_demand()).fold(
+            Demand2D {
+                width: Demand::zero(),
+                height: Demand::zero(),
+            },
+            |acc, d| Demand2D {
+                width: acc.width.max(d.width),
+                height: acc.height.max(d.height),
+            },
+        )
+    }
+
+    /// Draw the given widgets to the same window, one after another.
+    fn draw(&self, mut window: Window, hints: RenderingHints) {
+        for widget in self.widgets.iter() {
+            widget.draw(window.create_subwindow(.., ..), hints);
+        }
+    }
+}
+
 /// Variants on how to distinguish two neighboring widgets when drawing them to a window.
 #[derive(Clone)]
 pub enum SeparatingStyle {
@@ -475,6 +1033,8 @@ pub enum SeparatingStyle {
     AlternatingStyle(StyleModifier),
     /// Draw a line using the specified GraphemeCluster
     Draw(GraphemeCluster),
+    /// Leave the specified number of empty cells (filled with the background style) between them
+    Spacing(u32),
 }
 impl SeparatingStyle {
     /// The required additional width when using this style to separate widgets in a horizontal
@@ -484,6 +1044,7 @@ impl SeparatingStyle {
             &SeparatingStyle::None => Width::new(0).unwrap(),
             &SeparatingStyle::AlternatingStyle(_) => Width::new(0).unwrap(),
             &SeparatingStyle::Draw(ref cluster) => cluster.width().into(),
+            &SeparatingStyle::Spacing(n) => Width::new(n as i32).unwrap(),
         }
     }
     /// The required additional height when using this style to separate widgets in a vertical
@@ -493,6 +1054,7 @@ impl SeparatingStyle {
             &SeparatingStyle::None => Height::new(0).unwrap(),
             &SeparatingStyle::AlternatingStyle(_) => Height::new(0).unwrap(),
             &SeparatingStyle::Draw(_) => Height::new(1).unwrap(),
+            &SeparatingStyle::Spacing(n) => Height::new(n as i32).unwrap(),
         }
     }
 }
@@ -505,6 +1067,7 @@ mod test {
 
     use super::*;
     use base::test::FakeTerminal;
+    use widget::{ColDemand, RowDemand, WidgetExt};
 
     struct FakeWidget {
         space_demand: Demand2D,
@@ -1023,6 +1586,282 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_layout_background() {
+        let mut term = FakeTerminal::with_size((5, 1));
+        {
+            let mut window = term.create_root_window();
+            window.set_background(
+                GraphemeCluster::try_from('.').unwrap(),
+                StyleModifier::new(),
+            );
+            HLayout::new()
+                .widget("a")
+                .widget("b".with_demand(|_| Demand2D {
+                    width: Demand::at_least(1),
+                    height: Demand::exact(1),
+                }))
+                .draw(window, RenderingHints::default());
+        }
+        term.assert_looks_like("ab...");
+    }
+
+    #[test]
+    fn test_layout_separators() {
+        let c = |c| GraphemeCluster::try_from(c).unwrap();
+        let widget = |fill| FakeWidget::with_fill_char((Demand::exact(1), Demand::exact(1)), fill);
+
+        let layout = HLayout::new()
+            .separators(&[c('#')])
+            .widget(widget('1'))
+            .widget(widget('2'))
+            .widget(widget('3'));
+        assert_eq!(layout.space_demand().width, Demand::exact(6));
+        let mut term = FakeTerminal::with_size((5, 1));
+        layout.draw(term.create_root_window(), RenderingHints::default());
+        term.assert_looks_like("1#2 3");
+
+        let mut term = FakeTerminal::with_size((1, 5));
+        VLayout::new()
+            .separator(c('-'))
+            .separators(&[c('='), c('~')])
+            .widget(widget('1'))
+            .widget(widget('2'))
+            .widget(widget('3'))
+            .draw(term.create_root_window(), RenderingHints::default());
+        term.assert_looks_like("1|=|2|~|3");
+    }
+
+    #[test]
+    fn test_layout_shrink_priorities() {
+        let widget = |fill| FakeWidget::with_fill_char((Demand::exact(3), Demand::exact(1)), fill);
+        let draw = |layout: HLayout| {
+            let mut term = FakeTerminal::with_size((5, 1));
+            layout.draw(term.create_root_window(), RenderingHints::default());
+            term
+        };
+        let layout = || {
+            HLayout::new()
+                .widget(widget('1'))
+                .widget(widget('2'))
+                .widget(widget('3'))
+        };
+
+        draw(layout()).assert_looks_like("11223");
+        draw(layout().shrink_priorities(&[0, 1, 1])).assert_looks_like("22233");
+        draw(layout().shrink_priorities(&[1])).assert_looks_like("11122");
+        draw(layout().shrink_priorities(&[2, 0, 1])).assert_looks_like("11133");
+
+        let mut term = FakeTerminal::with_size((1, 2));
+        VLayout::new()
+            .shrink_priorities(&[0, 1])
+            .widget(FakeWidget::with_fill_char(
+                (Demand::exact(1), Demand::exact(2)),
+                '1',
+            ))
+            .widget(FakeWidget::with_fill_char(
+                (Demand::exact(1), Demand::exact(1)),
+                '2',
+            ))
+            .draw(term.create_root_window(), RenderingHints::default());
+        term.assert_looks_like("1|2");
+    }
+
+    #[test]
+    fn test_layout_hideable() {
+        let widget =
+            |fill| FakeWidget::with_fill_char((Demand::at_least(2), Demand::exact(1)), fill);
+        let layout = || {
+            HLayout::new()
+                .separator(GraphemeCluster::try_from('#').unwrap())
+                .widget(widget('1'))
+                .widget_hideable(widget('2'), Width::new(2).unwrap())
+                .widget_hideable(widget('3'), Width::new(2).unwrap())
+        };
+        assert_eq!(layout().space_demand().width, Demand::at_least(5));
+        let draw = |width| {
+            let mut term = FakeTerminal::with_size((width, 1));
+            layout().draw(term.create_root_window(), RenderingHints::default());
+            term
+        };
+        draw(8).assert_looks_like("11#22#33");
+        draw(7).assert_looks_like("111#222");
+        draw(4).assert_looks_like("1111");
+
+        let mut term = FakeTerminal::with_size((1, 2));
+        VLayout::new()
+            .widget_hideable(widget('1'), Height::new(2).unwrap())
+            .widget(FakeWidget::with_fill_char(
+                (Demand::exact(1), Demand::at_least(1)),
+                '2',
+            ))
+            .draw(term.create_root_window(), RenderingHints::default());
+        term.assert_looks_like("2|2");
+    }
+
+    #[test]
+    fn test_layout_reversed() {
+        let widget = |fill| FakeWidget::with_fill_char((Demand::exact(1), Demand::exact(1)), fill);
+        let mut term = FakeTerminal::with_size((6, 1));
+        HLayout::new()
+            .reversed()
+            .separator(GraphemeCluster::try_from('#').unwrap())
+            .separators(&[GraphemeCluster::try_from('+').unwrap()])
+            .widget(widget('1'))
+            .widget(widget('2'))
+            .widget(widget('3'))
+            .draw(term.create_root_window(), RenderingHints::default());
+        term.assert_looks_like(" 3#2+1");
+
+        let mut term = FakeTerminal::with_size((1, 3));
+        VLayout::new()
+            .reversed()
+            .widget(FakeWidget::with_fill_char(
+                (Demand::exact(1), Demand::at_least(2)),
+                '1',
+            ))
+            .widget(widget('2'))
+            .draw(term.create_root_window(), RenderingHints::default());
+        term.assert_looks_like("2|1|1");
+
+        let style = StyleModifier::new().bold(true);
+        let mut term = FakeTerminal::with_size((3, 1));
+        HLayout::new()
+            .reversed()
+            .alternating(style)
+            .widget(widget('1'))
+            .widget(widget('2'))
+            .draw(term.create_root_window(), RenderingHints::default());
+        term.assert_looks_like(" 2*1*");
+    }
+
+    #[test]
+    fn test_layout_spacing() {
+        let widget = |fill| FakeWidget::with_fill_char((Demand::exact(1), Demand::exact(1)), fill);
+        let layout = HLayout::new()
+            .separating_style(SeparatingStyle::Spacing(2))
+            .widget(widget('1'))
+            .widget(widget('2'));
+        assert_eq!(layout.space_demand().width, Demand::exact(6));
+        let mut term = FakeTerminal::from_str((6, 1), "xxxxxx").unwrap();
+        layout.draw(term.create_root_window(), RenderingHints::default());
+        term.assert_looks_like("1  2xx");
+
+        let mut term = FakeTerminal::with_size((1, 3));
+        VLayout::new()
+            .separating_style(SeparatingStyle::Spacing(1))
+            .widget(widget('1'))
+            .widget(widget('2'))
+            .draw(term.create_root_window(), RenderingHints::default());
+        term.assert_looks_like("1| |2");
+
+        let mut term = FakeTerminal::with_size((1, 7));
+        VLayout::new()
+            .separating_style(SeparatingStyle::Spacing(2))
+            .separators(&[GraphemeCluster::try_from('=').unwrap()])
+            .widget(widget('1'))
+            .widget(widget('2'))
+            .widget(widget('3'))
+            .draw(term.create_root_window(), RenderingHints::default());
+        term.assert_looks_like("1|=|=|2| | |3");
+    }
+
+    #[test]
+    fn test_stack_layout() {
+        let layout = StackLayout::new()
+            .widget(FakeWidget::with_fill_char(
+                (Demand::at_least(2), Demand::exact(2)),
+                '1',
+            ))
+            .widget(
+                FakeWidget::with_fill_char((Demand::exact(1), Demand::exact(1)), '2').centered(),
+            );
+        assert_eq!(
+            layout.space_demand(),
+            Demand2D {
+                width: Demand::at_least(2),
+                height: Demand::exact(2),
+            }
+        );
+        let mut term = FakeTerminal::with_size((3, 3));
+        layout.draw(term.create_root_window(), RenderingHints::default());
+        term.assert_looks_like("111|121|111");
+    }
+
+    #[test]
+    fn test_layout_proportionally() {
+        let lp = |space: i32, sep: i32, ratios: &[u32]| {
+            layout_proportionally(w(space), w(sep), ratios)
+                .iter()
+                .map(|l| l.raw_value())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(lp(8, 0, &[1, 2, 1]), vec![2, 4, 2]);
+        assert_eq!(lp(10, 1, &[1, 2, 1]), vec![2, 4, 2]);
+        assert_eq!(lp(5, 0, &[1, 1, 1]), vec![2, 2, 1]);
+        assert_eq!(lp(5, 0, &[0, 1, 1]), vec![0, 3, 2]);
+        assert_eq!(lp(3, 0, &[0, 0, 0]), vec![1, 1, 1]);
+        assert_eq!(lp(1, 1, &[1, 1]), vec![0, 0]);
+    }
+
+    #[test]
+    fn test_layout_with_ratios_draw() {
+        let mut term = FakeTerminal::with_size((8, 1));
+        HLayout::with_ratios(&[1, 2, 1])
+            .widget(FakeWidget::with_fill_char(
+                (Demand::exact(1), Demand::exact(1)),
+                '1',
+            ))
+            .widget(FakeWidget::with_fill_char(
+                (Demand::exact(1), Demand::exact(1)),
+                '2',
+            ))
+            .widget(FakeWidget::with_fill_char(
+                (Demand::at_least(5), Demand::exact(1)),
+                '3',
+            ))
+            .draw(term.create_root_window(), RenderingHints::default());
+        term.assert_looks_like("11222233");
+
+        let mut term = FakeTerminal::with_size((1, 5));
+        VLayout::with_ratios(&[2, 1])
+            .separator(GraphemeCluster::try_from('-').unwrap())
+            .widget(FakeWidget::with_fill_char(
+                (Demand::exact(1), Demand::exact(1)),
+                '1',
+            ))
+            .widget(FakeWidget::with_fill_char(
+                (Demand::exact(1), Demand::exact(1)),
+                '2',
+            ))
+            .draw(term.create_root_window(), RenderingHints::default());
+        term.assert_looks_like("1|1|1|-|2");
+    }
+
+    #[test]
+    fn fuzz_layout_proportionally() {
+        let fuzz_iterations = 10000;
+        let max_widgets = 10;
+        let max_space = 1000;
+        let max_separator_size = 5;
+        let max_ratio = 10;
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..fuzz_iterations {
+            let num_widgets = rng.gen_range(1, max_widgets);
+            let ratios = (0..num_widgets)
+                .map(|_| rng.gen_range(0, max_ratio))
+                .collect::<Vec<u32>>();
+            let space = rng.gen_range(0, max_space);
+            let separator_size = rng.gen_range(0, max_separator_size);
+            let layout = layout_proportionally(w(space), w(separator_size), &ratios);
+
+            let separator_space = (num_widgets as i32 - 1) * separator_size;
+            let assigned: i32 = layout.iter().map(|l| l.raw_value()).sum();
+            assert_eq!(assigned, ::std::cmp::max(space - separator_space, 0));
+        }
+    }
+
     #[test]
     fn fuzz_layout_linearly() {
         let fuzz_iterations = 10000;
@@ -50,6 +50,7 @@ pub mod widget;
 pub use self::layouts::*;
 pub use self::widget::*;
 use super::base::*;
+use std::ops::Range;
 
 /// Count the number of grapheme clusters in the given string.
 ///
@@ -66,3 +67,121 @@ pub fn text_width(text: &str) -> Width {
     use unicode_width::UnicodeWidthStr;
     Width::new(UnicodeWidthStr::width(text) as _).unwrap()
 }
+
+/// Convert the index of a grapheme cluster in the given string to the byte offset at which it
+/// starts.
+///
+/// The index one past the last grapheme cluster is mapped to the length of the string, all indices
+/// beyond that yield None.
+///
+/// # Examples:
+/// ```
+/// use unsegen::widget::grapheme_index_to_byte_offset;
+///
+/// assert_eq!(grapheme_index_to_byte_offset("löl", 0), Some(0));
+/// assert_eq!(grapheme_index_to_byte_offset("löl", 2), Some(3));
+/// assert_eq!(grapheme_index_to_byte_offset("löl", 3), Some(4));
+/// assert_eq!(grapheme_index_to_byte_offset("löl", 4), None);
+/// ```
+pub fn grapheme_index_to_byte_offset(text: &str, index: usize) -> Option<usize> {
+    use unicode_segmentation::UnicodeSegmentation;
+    text.grapheme_indices(true)
+        .map(|(offset, _)| offset)
+        .chain(Some(text.len()))
+        .nth(index)
+}
+
+/// Convert a byte offset in the given string to the index of the grapheme cluster that starts at
+/// this offset.
+///
+/// The length of the string is mapped to the index one past the last grapheme cluster. Offsets
+/// that do not correspond to the start of a grapheme cluster yield None.
+///
+/// # Examples:
+/// ```
+/// use unsegen::widget::byte_offset_to_grapheme_index;
+///
+/// assert_eq!(byte_offset_to_grapheme_index("löl", 0), Some(0));
+/// assert_eq!(byte_offset_to_grapheme_index("löl", 2), None);
+/// assert_eq!(byte_offset_to_grapheme_index("löl", 3), Some(2));
+/// assert_eq!(byte_offset_to_grapheme_index("löl", 4), Some(3));
+/// assert_eq!(byte_offset_to_grapheme_index("löl", 5), None);
+/// ```
+pub fn byte_offset_to_grapheme_index(text: &str, offset: usize) -> Option<usize> {
+    use unicode_segmentation::UnicodeSegmentation;
+    text.grapheme_indices(true)
+        .map(|(offset, _)| offset)
+        .chain(Some(text.len()))
+        .position(|o| o == offset)
+}
+
+/// Compute the part of the given string that is visible when it is scrolled horizontally by
+/// `offset` columns and displayed in `width` columns.
+///
+/// Only complete grapheme clusters are included in the returned slice. The two additional return
+/// values indicate whether a (wide) grapheme cluster straddles the left or right edge of the
+/// visible area, respectively, i.e., whether the corresponding edge cell has to be filled with
+/// something else.
+///
+/// # Examples:
+/// ```
+/// use unsegen::base::Width;
+/// use unsegen::widget::visible_slice;
+///
+/// let w = |i| Width::new(i).unwrap();
+/// assert_eq!(visible_slice("abcdef", w(1), w(3)), ("bcd", false, false));
+/// assert_eq!(visible_slice("a山b", w(2), w(2)), ("b", true, false));
+/// assert_eq!(visible_slice("a山b", w(0), w(2)), ("a", false, true));
+/// assert_eq!(visible_slice("ab", w(3), w(2)), ("", false, false));
+/// ```
+pub fn visible_slice(text: &str, offset: Width, width: Width) -> (&str, bool, bool) {
+    let (range, left_partial, right_partial) = visible_byte_range(text, offset, width);
+    (&text[range], left_partial, right_partial)
+}
+
+/// Compute the byte range of the given string that is visible when it is scrolled horizontally by
+/// `offset` columns and displayed in `width` columns.
+///
+/// This is the same as `visible_slice`, but returns the position of the visible part in `text`
+/// instead of the part itself.
+///
+/// # Examples:
+/// ```
+/// use unsegen::base::Width;
+/// use unsegen::widget::visible_byte_range;
+///
+/// let w = |i| Width::new(i).unwrap();
+/// assert_eq!(visible_byte_range("abcdef", w(1), w(3)), (1..4, false, false));
+/// assert_eq!(visible_byte_range("a山b", w(2), w(2)), (4..5, true, false));
+/// assert_eq!(visible_byte_range("ab", w(3), w(2)), (2..2, false, false));
+/// ```
+pub fn visible_byte_range(text: &str, offset: Width, width: Width) -> (Range<usize>, bool, bool) {
+    use unicode_segmentation::UnicodeSegmentation;
+    let offset: usize = offset.into();
+    let end: usize = offset + Into::<usize>::into(width);
+
+    let mut left_partial = false;
+    let mut right_partial = false;
+    let mut start_byte = None;
+    let mut end_byte = text.len();
+    let mut col = 0;
+    for (byte_index, grapheme) in text.grapheme_indices(true) {
+        let grapheme_start = col;
+        let grapheme_end = col + Into::<usize>::into(text_width(grapheme));
+        col = grapheme_end;
+        if grapheme_start < offset {
+            left_partial = grapheme_end > offset;
+            continue;
+        }
+        if grapheme_end > end {
+            right_partial = grapheme_start < end;
+            end_byte = byte_index;
+            break;
+        }
+        if start_byte.is_none() {
+            start_byte = Some(byte_index);
+        }
+    }
+    let start_byte = start_byte.unwrap_or(end_byte);
+    (start_byte..end_byte, left_partial, right_partial)
+}
@@ -1,6 +1,7 @@
 //! The `Widget` abstraction and some related types.
 use base::basic_types::*;
-use base::{Cursor, Window, WrappingMode};
+use base::{Cursor, GraphemeCluster, Window, WrappingMode};
+use std::cell::Cell;
 use std::cmp::max;
 use std::iter::Sum;
 use std::marker::PhantomData;
@@ -35,6 +36,69 @@ pub trait WidgetExt: Widget + Sized {
         Centered(self)
     }
 
+    /// Restrict the widget to at most the specified width. If the window is wider, the widget is
+    /// drawn centered horizontally and the remaining columns on either side are cleared.
+    fn max_width<I: Into<Width>>(self, width: I) -> MaxWidth<Self> {
+        MaxWidth(self, width.into())
+    }
+
+    /// Pin the widget to exactly the specified size. If the window is larger, the widget is drawn
+    /// centered and the surrounding area is cleared.
+    fn fixed_size(self, width: Width, height: Height) -> FixedSize<Self> {
+        FixedSize(self, width, height)
+    }
+
+    /// Only draw the widget if the window satisfies its minimum demand. Otherwise, a message
+    /// stating the required size is drawn instead.
+    fn require_min_size(self) -> RequireMinSize<Self> {
+        RequireMinSize(self)
+    }
+
+    /// Surround the widget with empty space: `horizontal` columns on the left and right and
+    /// `vertical` rows on the top and bottom. Individual sides can be adjusted using the methods
+    /// of `Padding`.
+    fn padding(self, horizontal: Width, vertical: Height) -> Padding<Self> {
+        Padding {
+            widget: self,
+            left: horizontal,
+            right: horizontal,
+            top: vertical,
+            bottom: vertical,
+        }
+    }
+
+    /// Draw a box around the widget using box-drawing characters. The glyphs and an optional
+    /// title can be specified using the methods of `Border`.
+    fn border(self) -> Border<Self> {
+        Border {
+            widget: self,
+            title: String::new(),
+            horizontal: GraphemeCluster::try_from('─').unwrap(),
+            vertical: GraphemeCluster::try_from('│').unwrap(),
+            corners: [
+                GraphemeCluster::try_from('┌').unwrap(),
+                GraphemeCluster::try_from('┐').unwrap(),
+                GraphemeCluster::try_from('└').unwrap(),
+                GraphemeCluster::try_from('┘').unwrap(),
+            ],
+        }
+    }
+
+    /// Position the widget within the supplied window according to its maximum demand if the
+    /// window is larger than that. The remaining area is cleared.
+    fn aligned(self, horizontal: Alignment, vertical: VerticalAlignment) -> Aligned<Self> {
+        Aligned(self, horizontal, vertical)
+    }
+
+    /// Only compute the demand of the widget once and report the cached value until it is
+    /// invalidated. See `CachedDemand`.
+    fn cached_demand(self) -> CachedDemand<Self> {
+        CachedDemand {
+            widget: self,
+            demand: Cell::new(None),
+        }
+    }
+
     /// Alter the window before letting the widget draw itself in it.
     fn with_window<F: Fn(Window, RenderingHints) -> Window>(self, f: F) -> WithWindow<Self, F> {
         WithWindow(self, f)
@@ -89,6 +153,436 @@ impl<W: Widget> Widget for Centered<W> {
     }
 }
 
+/// Restrict the widget to at most the specified width and center it horizontally within the
+/// supplied window. The remaining columns on either side are cleared.
+///
+/// In contrast to `Centered`, the maximum width is a constraint imposed from the outside rather
+/// than the demand of the wrapped widget. This is useful, for example, for a reading pane with a
+/// limited line length even on very wide terminals.
+///
+/// This wrapper can be created using `WidgetExt::max_width`.
+pub struct MaxWidth<W>(W, Width);
+
+impl<W: Widget> Widget for MaxWidth<W> {
+    fn space_demand(&self) -> Demand2D {
+        let demand = self.0.space_demand();
+        let max_width = self.1;
+        Demand2D {
+            width: Demand::from_to(
+                demand.width.min.min(max_width),
+                demand.width.max.unwrap_or(max_width).min(max_width),
+            ),
+            height: demand.height,
+        }
+    }
+    fn draw(&self, mut window: Window, hints: RenderingHints) {
+        window.clear();
+
+        let window_width = window.get_width();
+        let content_width = self.1.min(window_width);
+
+        let start_col = ((window_width - content_width) / 2).from_origin();
+        let end_col = start_col + content_width;
+
+        let window = window.create_subwindow(start_col..end_col, ..);
+        self.0.draw(window, hints);
+    }
+}
+
+/// Pin the widget to exactly the specified size and center it within the supplied window. The
+/// surrounding area is cleared.
+///
+/// This is useful, for example, for dialogs or panels of a fixed size.
+///
+/// This wrapper can be created using `WidgetExt::fixed_size`.
+pub struct FixedSize<W>(W, Width, Height);
+
+impl<W: Widget> Widget for FixedSize<W> {
+    fn space_demand(&self) -> Demand2D {
+        Demand2D {
+            width: Demand::exact(self.1),
+            height: Demand::exact(self.2),
+        }
+    }
+    fn draw(&self, mut window: Window, hints: RenderingHints) {
+        window.clear();
+
+        let window_width = window.get_width();
+        let window_height = window.get_height();
+        let width = self.1.min(window_width);
+        let height = self.2.min(window_height);
+
+        let start_col = ((window_width - width) / 2).from_origin();
+        let start_row = ((window_height - height) / 2).from_origin();
+
+        let window =
+            window.create_subwindow(start_col..start_col + width, start_row..start_row + height);
+        self.0.draw(window, hints);
+    }
+}
+
+/// Only draw the widget if the window is at least as large as the minimum demand of the widget.
+/// Otherwise, the window is cleared and a message like `terminal too small (need 80x24)` is drawn
+/// instead.
+///
+/// This is most useful as the outermost widget of an application: The minimum demand of a layout
+/// already accounts for all of its children, so instead of squeezing some of them into zero space,
+/// the user is told how much space is needed.
+///
+/// This wrapper can be created using `WidgetExt::require_min_size`.
+pub struct RequireMinSize<W>(W);
+
+impl<W: Widget> Widget for RequireMinSize<W> {
+    fn space_demand(&self) -> Demand2D {
+        self.0.space_demand()
+    }
+    fn draw(&self, mut window: Window, hints: RenderingHints) {
+        let demand = self.space_demand();
+        if window.get_width() >= demand.width.min && window.get_height() >= demand.height.min {
+            self.0.draw(window, hints);
+        } else {
+            use std::fmt::Write;
+            window.clear();
+            let mut cursor = Cursor::new(&mut window).wrapping_mode(WrappingMode::Wrap);
+            let _ = write!(
+                cursor,
+                "terminal too small (need {}x{})",
+                demand.width.min.raw_value(),
+                demand.height.min.raw_value()
+            );
+        }
+    }
+}
+
+/// Surround the widget with (cleared) empty space on each side.
+///
+/// If the window is too small to fit the padding, the padding is reduced (starting with the right
+/// and bottom sides) and the widget is drawn into whatever space remains, if any.
+///
+/// This wrapper can be created using `WidgetExt::padding`.
+///
+/// # Examples:
+/// ```
+/// use unsegen::base::*;
+/// use unsegen::widget::*;
+///
+/// let widget = "content"
+///     .padding(Width::new(2).unwrap(), Height::new(0).unwrap())
+///     .top(Height::new(1).unwrap());
+/// assert_eq!(widget.space_demand().width, ColDemand::exact(11));
+/// assert_eq!(widget.space_demand().height, RowDemand::exact(2));
+/// ```
+pub struct Padding<W> {
+    widget: W,
+    left: Width,
+    right: Width,
+    top: Height,
+    bottom: Height,
+}
+
+impl<W> Padding<W> {
+    /// Specify the number of empty columns to the left of the widget.
+    pub fn left(mut self, width: Width) -> Self {
+        self.left = width;
+        self
+    }
+
+    /// Specify the number of empty columns to the right of the widget.
+    pub fn right(mut self, width: Width) -> Self {
+        self.right = width;
+        self
+    }
+
+    /// Specify the number of empty rows above the widget.
+    pub fn top(mut self, height: Height) -> Self {
+        self.top = height;
+        self
+    }
+
+    /// Specify the number of empty rows below the widget.
+    pub fn bottom(mut self, height: Height) -> Self {
+        self.bottom = height;
+        self
+    }
+}
+
+impl<W: Widget> Widget for Padding<W> {
+    fn space_demand(&self) -> Demand2D {
+        let demand = self.widget.space_demand();
+        Demand2D {
+            width: demand.width + Demand::exact(self.left + self.right),
+            height: demand.height + Demand::exact(self.top + self.bottom),
+        }
+    }
+    fn draw(&self, mut window: Window, hints: RenderingHints) {
+        window.clear();
+
+        let window_width = window.get_width();
+        let window_height = window.get_height();
+
+        let start_col = self.left.min(window_width).from_origin();
+        let end_col = (window_width - self.right)
+            .from_origin()
+            .positive_or_zero()
+            .max(start_col);
+        let start_row = self.top.min(window_height).from_origin();
+        let end_row = (window_height - self.bottom)
+            .from_origin()
+            .positive_or_zero()
+            .max(start_row);
+
+        let window = window.create_subwindow(start_col..end_col, start_row..end_row);
+        self.widget.draw(window, hints);
+    }
+}
+
+/// Draw a box (with an optional title on the top edge) around the widget, which is drawn into the
+/// remaining inner window.
+///
+/// All glyphs are expected to be one cell wide.
+///
+/// This wrapper can be created using `WidgetExt::border`.
+///
+/// # Examples:
+/// ```
+/// # use unsegen::base::terminal::test::FakeTerminal;
+/// use unsegen::widget::*;
+///
+/// let widget = "content".border().with_title("Title");
+///
+/// let mut term = FakeTerminal::with_size((9, 3));
+/// widget.draw(term.create_root_window(), RenderingHints::new());
+/// term.assert_looks_like("┌Title──┐|│content│|└───────┘");
+/// ```
+pub struct Border<W> {
+    widget: W,
+    title: String,
+    horizontal: GraphemeCluster,
+    vertical: GraphemeCluster,
+    corners: [GraphemeCluster; 4],
+}
+
+impl<W> Border<W> {
+    /// Specify a title that is drawn on the top edge of the box. It is truncated if the box is not
+    /// wide enough. (Default: no title)
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.title = title.to_owned();
+        self
+    }
+
+    /// Specify the glyphs for the horizontal (top and bottom) and vertical (left and right) edges.
+    /// (Default: '─' and '│')
+    pub fn edges(mut self, horizontal: GraphemeCluster, vertical: GraphemeCluster) -> Self {
+        self.horizontal = horizontal;
+        self.vertical = vertical;
+        self
+    }
+
+    /// Specify the glyphs for the top left, top right, bottom left and bottom right corners.
+    /// (Default: '┌', '┐', '└' and '┘')
+    pub fn corners(
+        mut self,
+        top_left: GraphemeCluster,
+        top_right: GraphemeCluster,
+        bottom_left: GraphemeCluster,
+        bottom_right: GraphemeCluster,
+    ) -> Self {
+        self.corners = [top_left, top_right, bottom_left, bottom_right];
+        self
+    }
+}
+
+impl<W: Widget> Widget for Border<W> {
+    fn space_demand(&self) -> Demand2D {
+        let demand = self.widget.space_demand();
+        Demand2D {
+            width: demand.width + Demand::exact(2),
+            height: demand.height + Demand::exact(2),
+        }
+    }
+    fn draw(&self, mut window: Window, hints: RenderingHints) {
+        use unicode_segmentation::UnicodeSegmentation;
+
+        window.clear();
+
+        let width = window.get_width().raw_value();
+        let height = window.get_height().raw_value();
+        {
+            let mut cursor = Cursor::new(&mut window);
+            for y in 0..height {
+                for x in 0..width {
+                    let (left, right, top, bottom) =
+                        (x == 0, x == width - 1, y == 0, y == height - 1);
+                    let glyph = match (left, right, top, bottom) {
+                        (true, _, true, _) => &self.corners[0],
+                        (_, true, true, _) => &self.corners[1],
+                        (true, _, _, true) => &self.corners[2],
+                        (_, true, _, true) => &self.corners[3],
+                        (_, _, true, _) | (_, _, _, true) => &self.horizontal,
+                        (true, _, _, _) | (_, true, _, _) => &self.vertical,
+                        _ => continue,
+                    };
+                    cursor.move_to(ColIndex::new(x), RowIndex::new(y));
+                    cursor.write(glyph.as_str());
+                }
+            }
+
+            // Only write grapheme clusters of the title that fit between the corners.
+            let mut remaining = width - 2;
+            cursor.move_to(ColIndex::new(1), RowIndex::new(0));
+            for grapheme in self.title.graphemes(true) {
+                let grapheme_width = crate::widget::text_width(grapheme).raw_value();
+                if grapheme_width > remaining {
+                    break;
+                }
+                remaining -= grapheme_width;
+                cursor.write(grapheme);
+            }
+        }
+
+        let start_col = ColIndex::new(width.min(1));
+        let end_col = ColIndex::new(width - 1).max(start_col);
+        let start_row = RowIndex::new(height.min(1));
+        let end_row = RowIndex::new(height - 1).max(start_row);
+        let window = window.create_subwindow(start_col..end_col, start_row..end_row);
+        self.widget.draw(window, hints);
+    }
+}
+
+/// Horizontal position of content within a window that is wider than the content.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Alignment {
+    /// Place the content at the leftmost column.
+    Left,
+    /// Place the content with (roughly) the same amount of space on either side.
+    Center,
+    /// Place the content flush with the rightmost column.
+    Right,
+}
+
+/// Vertical position of content within a window that is higher than the content.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VerticalAlignment {
+    /// Place the content at the topmost row.
+    Top,
+    /// Place the content with (roughly) the same amount of space above and below.
+    Center,
+    /// Place the content flush with the bottommost row.
+    Bottom,
+}
+
+/// Position the widget within the supplied window according to its maximum demand if the window is
+/// larger than that. The remaining area is cleared.
+///
+/// In contrast to `Centered`, the position can be chosen independently for both axes, e.g., to
+/// place an exact-size label in the bottom right corner of a larger layout cell. Axes without a
+/// maximum demand are filled completely.
+///
+/// This wrapper can be created using `WidgetExt::aligned`.
+///
+/// # Examples:
+/// ```
+/// # use unsegen::base::terminal::test::FakeTerminal;
+/// use unsegen::widget::*;
+///
+/// let widget = "ab".aligned(Alignment::Right, VerticalAlignment::Bottom);
+///
+/// let mut term = FakeTerminal::with_size((4, 2));
+/// widget.draw(term.create_root_window(), RenderingHints::new());
+/// term.assert_looks_like("    |  ab");
+/// ```
+pub struct Aligned<W>(W, Alignment, VerticalAlignment);
+
+impl<W: Widget> Widget for Aligned<W> {
+    fn space_demand(&self) -> Demand2D {
+        self.0.space_demand()
+    }
+    fn draw(&self, mut window: Window, hints: RenderingHints) {
+        window.clear();
+
+        let demand = self.space_demand();
+        let window_width = window.get_width();
+        let window_height = window.get_height();
+        let width = demand.width.max.unwrap_or(window_width).min(window_width);
+        let height = demand
+            .height
+            .max
+            .unwrap_or(window_height)
+            .min(window_height);
+
+        let start_col = match self.1 {
+            Alignment::Left => ColIndex::new(0),
+            Alignment::Center => ((window_width - width) / 2).from_origin(),
+            Alignment::Right => (window_width - width).from_origin(),
+        };
+        let start_row = match self.2 {
+            VerticalAlignment::Top => RowIndex::new(0),
+            VerticalAlignment::Center => ((window_height - height) / 2).from_origin(),
+            VerticalAlignment::Bottom => (window_height - height).from_origin(),
+        };
+
+        let window =
+            window.create_subwindow(start_col..start_col + width, start_row..start_row + height);
+        self.0.draw(window, hints);
+    }
+}
+
+/// Cache the demand of the widget, which is only recomputed after calling `invalidate` (or
+/// mutably accessing the widget via `inner_mut`). Drawing is forwarded unchanged.
+///
+/// This is useful for widgets whose demand is expensive to compute, but rarely changes, e.g.,
+/// because it requires a dry-run draw of a large amount of content. In contrast to most other
+/// wrappers, this one has to be kept across frames.
+///
+/// This wrapper can be created using `WidgetExt::cached_demand`.
+///
+/// # Examples:
+/// ```
+/// use unsegen::widget::*;
+///
+/// let mut widget = "foo".to_owned().cached_demand();
+/// assert_eq!(widget.space_demand().width, ColDemand::exact(3));
+///
+/// widget.inner_mut().push_str("bar");
+/// assert_eq!(widget.space_demand().width, ColDemand::exact(6));
+/// ```
+pub struct CachedDemand<W> {
+    widget: W,
+    demand: Cell<Option<Demand2D>>,
+}
+
+impl<W> CachedDemand<W> {
+    /// Discard the cached demand so that it is recomputed when it is requested the next time.
+    pub fn invalidate(&self) {
+        self.demand.set(None);
+    }
+
+    /// Get a reference to the wrapped widget.
+    pub fn inner(&self) -> &W {
+        &self.widget
+    }
+
+    /// Get a mutable reference to the wrapped widget. The cached demand is invalidated.
+    pub fn inner_mut(&mut self) -> &mut W {
+        self.invalidate();
+        &mut self.widget
+    }
+}
+
+impl<W: Widget> Widget for CachedDemand<W> {
+    fn space_demand(&self) -> Demand2D {
+        if let Some(demand) = self.demand.get() {
+            return demand;
+        }
+        let demand = self.widget.space_demand();
+        self.demand.set(Some(demand));
+        demand
+    }
+    fn draw(&self, window: Window, hints: RenderingHints) {
+        self.widget.draw(window, hints);
+    }
+}
+
 /// Alter the window before letting the widget draw itself in it.
 ///
 /// This wrapper can be created using `WidgetExt::with_window`.
@@ -132,6 +626,16 @@ impl<W: Widget, F: Fn(Demand2D) -> Demand2D> Widget for WithDemand<W, F> {
     }
 }
 
+/// Owned trait objects can be used as widgets directly, e.g., to add them to a layout.
+impl<'a> Widget for Box<dyn Widget + 'a> {
+    fn space_demand(&self) -> Demand2D {
+        (**self).space_demand()
+    }
+    fn draw(&self, window: Window, hints: RenderingHints) {
+        (**self).draw(window, hints);
+    }
+}
+
 impl<S: std::convert::AsRef<str>> Widget for S {
     fn space_demand(&self) -> Demand2D {
         let mut width = 0;
@@ -413,4 +917,236 @@ impl Demand2D {
             height: self.height.max(other.height),
         }
     }
+
+    /// Split the `Demand2D` into its horizontal and vertical component, e.g., to feed them into
+    /// `layout_linearly` separately.
+    pub fn split_axes(self) -> (ColDemand, RowDemand) {
+        (self.width, self.height)
+    }
+
+    /// Calculate the combined `Demand2D` of widgets arranged from left to right, as done by
+    /// `HLayout`.
+    ///
+    /// The widths are accumulated, the height accommodates all demands and `separator_width` is
+    /// reserved additionally for every element.
+    ///
+    /// # Examples:
+    /// ```
+    /// use unsegen::base::*;
+    /// use unsegen::widget::*;
+    ///
+    /// let demands = [
+    ///     Demand2D {
+    ///         width: ColDemand::exact(5),
+    ///         height: RowDemand::exact(5),
+    ///     },
+    ///     Demand2D {
+    ///         width: ColDemand::at_least(2),
+    ///         height: RowDemand::from_to(3, 5),
+    ///     },
+    /// ];
+    ///
+    /// assert_eq!(
+    ///     Demand2D::aggregate_horizontal(&demands, Width::new(1).unwrap()),
+    ///     Demand2D {
+    ///         width: ColDemand::at_least(9),
+    ///         height: RowDemand::exact(5),
+    ///     }
+    /// );
+    /// ```
+    pub fn aggregate_horizontal(demands: &[Demand2D], separator_width: Width) -> Self {
+        let mut total = Demand2D {
+            width: Demand::exact(0),
+            height: Demand::exact(0),
+        };
+        for demand in demands {
+            total = total.add_horizontal(*demand);
+        }
+        total.width += Demand::exact(separator_width * demands.len());
+        total
+    }
+
+    /// Calculate the combined `Demand2D` of widgets arranged from top to bottom, as done by
+    /// `VLayout`.
+    ///
+    /// The heights are accumulated, the width accommodates all demands and `separator_height` is
+    /// reserved additionally for every element.
+    ///
+    /// # Examples:
+    /// ```
+    /// use unsegen::base::*;
+    /// use unsegen::widget::*;
+    ///
+    /// let demands = [
+    ///     Demand2D {
+    ///         width: ColDemand::exact(5),
+    ///         height: RowDemand::exact(5),
+    ///     },
+    ///     Demand2D {
+    ///         width: ColDemand::at_least(2),
+    ///         height: RowDemand::from_to(3, 5),
+    ///     },
+    /// ];
+    ///
+    /// assert_eq!(
+    ///     Demand2D::aggregate_vertical(&demands, Height::new(0).unwrap()),
+    ///     Demand2D {
+    ///         width: ColDemand::at_least(5),
+    ///         height: RowDemand::from_to(8, 10),
+    ///     }
+    /// );
+    /// ```
+    pub fn aggregate_vertical(demands: &[Demand2D], separator_height: Height) -> Self {
+        let mut total = Demand2D {
+            width: Demand::exact(0),
+            height: Demand::exact(0),
+        };
+        for demand in demands {
+            total = total.add_vertical(*demand);
+        }
+        total.height += Demand::exact(separator_height * demands.len());
+        total
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use base::test::FakeTerminal;
+
+    #[test]
+    fn test_max_width() {
+        let widget = "abcdef".max_width(Width::new(4).unwrap());
+        assert_eq!(widget.space_demand().width, ColDemand::exact(4));
+
+        let mut term = FakeTerminal::from_str((8, 1), "xxxxxxxx").unwrap();
+        widget.draw(term.create_root_window(), RenderingHints::default());
+        term.assert_looks_like("  abcd  ");
+    }
+
+    #[test]
+    fn test_fixed_size() {
+        let widget = "ab\ncd".fixed_size(Width::new(2).unwrap(), Height::new(1).unwrap());
+        assert_eq!(widget.space_demand().width, ColDemand::exact(2));
+        assert_eq!(widget.space_demand().height, RowDemand::exact(1));
+
+        let mut term = FakeTerminal::from_str((4, 3), "xxxx xxxx xxxx").unwrap();
+        widget.draw(term.create_root_window(), RenderingHints::default());
+        term.assert_looks_like("    | ab |    ");
+    }
+
+    #[test]
+    fn test_padding() {
+        let widget = "ab"
+            .padding(Width::new(1).unwrap(), Height::new(1).unwrap())
+            .right(Width::new(2).unwrap());
+        assert_eq!(widget.space_demand().width, ColDemand::exact(5));
+        assert_eq!(widget.space_demand().height, RowDemand::exact(3));
+
+        let mut term = FakeTerminal::from_str((5, 3), "xxxxx xxxxx xxxxx").unwrap();
+        widget.draw(term.create_root_window(), RenderingHints::default());
+        term.assert_looks_like("     | ab  |     ");
+
+        // The padding is reduced if the window is too small.
+        let mut term = FakeTerminal::from_str((2, 2), "xx xx").unwrap();
+        widget.draw(term.create_root_window(), RenderingHints::default());
+        term.assert_looks_like("  |  ");
+
+        let mut term = FakeTerminal::from_str((4, 3), "xxxx xxxx xxxx").unwrap();
+        widget.draw(term.create_root_window(), RenderingHints::default());
+        term.assert_looks_like("    | a  |    ");
+    }
+
+    #[test]
+    fn test_border() {
+        let widget = "ab".border();
+        assert_eq!(widget.space_demand().width, ColDemand::exact(4));
+        assert_eq!(widget.space_demand().height, RowDemand::exact(3));
+
+        let mut term = FakeTerminal::from_str((4, 3), "xxxx xxxx xxxx").unwrap();
+        widget.draw(term.create_root_window(), RenderingHints::default());
+        term.assert_looks_like("┌──┐|│ab│|└──┘");
+
+        let widget = "a"
+            .border()
+            .with_title("漢title")
+            .edges(
+                GraphemeCluster::try_from('-').unwrap(),
+                GraphemeCluster::try_from(':').unwrap(),
+            )
+            .corners(
+                GraphemeCluster::try_from('1').unwrap(),
+                GraphemeCluster::try_from('2').unwrap(),
+                GraphemeCluster::try_from('3').unwrap(),
+                GraphemeCluster::try_from('4').unwrap(),
+            );
+        let mut term = FakeTerminal::with_size((5, 4));
+        widget.draw(term.create_root_window(), RenderingHints::default());
+        term.assert_looks_like("1漢t2|:a  :|:   :|3---4");
+
+        // The title does not overwrite the corners even if it does not fit.
+        let mut term = FakeTerminal::with_size((3, 2));
+        widget.draw(term.create_root_window(), RenderingHints::default());
+        term.assert_looks_like("1-2|3-4");
+    }
+
+    #[test]
+    fn test_aligned() {
+        let widget = "ab".aligned(Alignment::Center, VerticalAlignment::Center);
+        let mut term = FakeTerminal::from_str((4, 3), "xxxx xxxx xxxx").unwrap();
+        widget.draw(term.create_root_window(), RenderingHints::default());
+        term.assert_looks_like("    | ab |    ");
+
+        let widget = "ab".aligned(Alignment::Right, VerticalAlignment::Bottom);
+        let mut term = FakeTerminal::from_str((5, 3), "xxxxx xxxxx xxxxx").unwrap();
+        widget.draw(term.create_root_window(), RenderingHints::default());
+        term.assert_looks_like("     |     |   ab");
+
+        // Axes without a maximum demand are filled.
+        let widget = "ab"
+            .with_demand(|d| Demand2D {
+                width: Demand::at_least(2),
+                height: d.height,
+            })
+            .aligned(Alignment::Right, VerticalAlignment::Top);
+        let mut term = FakeTerminal::from_str((4, 2), "xxxx xxxx").unwrap();
+        widget.draw(term.create_root_window(), RenderingHints::default());
+        term.assert_looks_like("ab  |    ");
+    }
+
+    #[test]
+    fn test_cached_demand() {
+        struct CountingWidget(Cell<usize>);
+        impl Widget for CountingWidget {
+            fn space_demand(&self) -> Demand2D {
+                self.0.set(self.0.get() + 1);
+                Demand2D {
+                    width: Demand::exact(self.0.get()),
+                    height: Demand::exact(1),
+                }
+            }
+            fn draw(&self, _window: Window, _hints: RenderingHints) {}
+        }
+
+        let widget = CountingWidget(Cell::new(0)).cached_demand();
+        assert_eq!(widget.space_demand().width, ColDemand::exact(1));
+        assert_eq!(widget.space_demand().width, ColDemand::exact(1));
+        assert_eq!(widget.inner().0.get(), 1);
+        widget.invalidate();
+        assert_eq!(widget.space_demand().width, ColDemand::exact(2));
+        assert_eq!(widget.space_demand().width, ColDemand::exact(2));
+    }
+
+    #[test]
+    fn test_require_min_size() {
+        let widget = "abcdefghijkl".require_min_size();
+
+        let mut term = FakeTerminal::with_size((10, 3));
+        widget.draw(term.create_root_window(), RenderingHints::default());
+        term.assert_looks_like("terminal t|oo small (|need 12x1)");
+
+        let mut term = FakeTerminal::with_size((12, 1));
+        widget.draw(term.create_root_window(), RenderingHints::default());
+        term.assert_looks_like("abcdefghijkl");
+    }
 }